@@ -0,0 +1,206 @@
+use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired, AudioSubsystem};
+
+const SAMPLE_RATE: i32 = 44_100;
+const VOLUME: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Beep {
+    // A short beep for each of the last few seconds
+    Tick,
+    // A longer, higher beep for when the timer hits zero
+    Final,
+    // A very short beep for each beat of the metronome
+    Click,
+}
+
+impl Beep {
+    fn frequency(&self) -> f32 {
+        match self {
+            Beep::Tick => 880.0,
+            Beep::Final => 1760.0,
+            Beep::Click => 1320.0,
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        match self {
+            Beep::Tick => 0.15,
+            Beep::Final => 0.8,
+            Beep::Click => 0.03,
+        }
+    }
+}
+
+struct SquareWave {
+    sample_rate: f32,
+    frequency: f32,
+    phase: f32,
+    samples_left: u32,
+    // How many samples have played since the beep started, and over how many the
+    // volume ramps up to full. A ramp of zero plays at full volume straight away.
+    samples_played: u32,
+    fade_in_samples: u32,
+}
+
+impl AudioCallback for SquareWave {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        for sample in out.iter_mut() {
+            if self.samples_left == 0 {
+                *sample = 0.0;
+                continue;
+            }
+
+            let volume = VOLUME
+                * fade_in_volume(
+                    self.samples_played as f32 / self.sample_rate,
+                    self.fade_in_samples as f32 / self.sample_rate,
+                );
+
+            *sample = match self.phase < 0.5 {
+                true => volume,
+                _ => -volume,
+            };
+            self.phase = (self.phase + self.frequency / self.sample_rate) % 1.0;
+            self.samples_left -= 1;
+            self.samples_played += 1;
+        }
+    }
+}
+
+pub struct Beeper {
+    device: AudioDevice<SquareWave>,
+}
+
+impl Beeper {
+    /// Open the named playback device, or the system's default one when there's no name
+    pub fn open(audio_subsystem: &AudioSubsystem, device: Option<&str>) -> Result<Beeper, String> {
+        let spec = AudioSpecDesired {
+            freq: Some(SAMPLE_RATE),
+            channels: Some(1),
+            samples: None,
+        };
+
+        let device = audio_subsystem.open_playback(device, &spec, |spec| SquareWave {
+            sample_rate: spec.freq as f32,
+            frequency: 0.0,
+            phase: 0.0,
+            samples_left: 0,
+            samples_played: 0,
+            fade_in_samples: 0,
+        })?;
+
+        // The device always plays; it's silent until it's given a beep
+        device.resume();
+
+        Ok(Beeper { device })
+    }
+
+    pub fn beep(&mut self, beep: Beep) {
+        self.fade_in(beep, 0.0);
+    }
+
+    /// Play the beep starting quietly and getting louder over `fade_seconds`.
+    /// The beep is held at full volume for its usual length once the fade is done.
+    pub fn fade_in(&mut self, beep: Beep, fade_seconds: f32) {
+        let mut wave = self.device.lock();
+        wave.frequency = beep.frequency();
+        wave.phase = 0.0;
+        wave.samples_played = 0;
+        wave.fade_in_samples = (fade_seconds * wave.sample_rate) as u32;
+        wave.samples_left = ((fade_seconds + beep.duration()) * wave.sample_rate) as u32;
+    }
+}
+
+/// The names of the playback devices that SDL can see
+pub fn playback_devices(audio_subsystem: &AudioSubsystem) -> Vec<String> {
+    (0..audio_subsystem.num_audio_playback_devices().unwrap_or(0))
+        .filter_map(|index| audio_subsystem.audio_playback_device_name(index).ok())
+        .collect()
+}
+
+/// Find the device with the given name, preferring an exact match
+/// but falling back to one that only differs by case.
+pub fn find_device<'a>(devices: &'a [String], name: &str) -> Result<&'a str, String> {
+    devices
+        .iter()
+        .find(|device| *device == name)
+        .or_else(|| {
+            devices
+                .iter()
+                .find(|device| device.eq_ignore_ascii_case(name))
+        })
+        .map(|device| device.as_str())
+        .ok_or(format!(
+            "Invalid audio device: \"{}\" (see --list-audio-devices)",
+            name
+        ))
+}
+
+/// How loud a fading in beep is after `elapsed` seconds, from 0 to 1. The volume
+/// rises with the square of the time so that it sounds like an even ramp.
+pub fn fade_in_volume(elapsed: f32, fade_seconds: f32) -> f32 {
+    match fade_seconds > 0.0 {
+        true => (elapsed / fade_seconds).clamp(0.0, 1.0).powi(2),
+        _ => 1.0,
+    }
+}
+
+/// Find the beep to play, if any, as a countdown goes from `previous` to `current`
+/// seconds remaining. A tick plays as each of the last `beeps` whole seconds is
+/// crossed, and the final beep plays when the countdown reaches zero.
+pub fn countdown_beep(previous: f64, current: f64, beeps: u32) -> Option<Beep> {
+    if previous > 0.0 && current <= 0.0 {
+        return Some(Beep::Final);
+    }
+
+    (1..=beeps)
+        .any(|second| previous > second as f64 && current <= second as f64)
+        .then_some(Beep::Tick)
+}
+
+#[test]
+fn it_should_tick_when_crossing_each_of_the_last_seconds() {
+    assert_eq!(Some(Beep::Tick), countdown_beep(3.01, 2.99, 3));
+    assert_eq!(Some(Beep::Tick), countdown_beep(2.01, 2.0, 3));
+    assert_eq!(Some(Beep::Tick), countdown_beep(1.02, 0.99, 3));
+}
+
+#[test]
+fn it_should_play_the_final_beep_at_zero() {
+    assert_eq!(Some(Beep::Final), countdown_beep(0.01, -0.01, 3));
+    assert_eq!(Some(Beep::Final), countdown_beep(0.01, 0.0, 0));
+}
+
+#[test]
+fn it_should_not_beep_outside_of_the_final_seconds() {
+    assert_eq!(None, countdown_beep(4.01, 3.99, 3));
+    assert_eq!(None, countdown_beep(2.5, 2.4, 3));
+    assert_eq!(None, countdown_beep(0.0, -0.01, 3));
+}
+
+#[test]
+fn it_should_ramp_the_volume_up_over_the_fade() {
+    assert_eq!(0.0, fade_in_volume(0.0, 2.0));
+    assert_eq!(0.25, fade_in_volume(1.0, 2.0));
+    assert_eq!(1.0, fade_in_volume(2.0, 2.0));
+    assert_eq!(1.0, fade_in_volume(5.0, 2.0));
+}
+
+#[test]
+fn it_should_play_at_full_volume_without_a_fade() {
+    assert_eq!(1.0, fade_in_volume(0.0, 0.0));
+}
+
+#[test]
+fn it_should_find_an_audio_device_by_name() {
+    let devices = vec!["Built-in Speakers".to_string(), "USB Headset".to_string()];
+
+    assert_eq!(Ok("USB Headset"), find_device(&devices, "USB Headset"));
+    assert_eq!(
+        Ok("Built-in Speakers"),
+        find_device(&devices, "built-in speakers")
+    );
+    assert!(find_device(&devices, "HDMI").is_err());
+}
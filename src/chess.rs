@@ -0,0 +1,140 @@
+use crate::clock::Clock;
+use crate::parse_timer;
+use crate::state::{TimerMode, TimerState};
+
+#[cfg(test)]
+use crate::clock::FakeClock;
+
+/// Parse the time each player gets, with an optional increment that's added
+/// on after each of their moves (e.g. "5m" or "5m+3s")
+pub fn parse_chess(value: &str) -> Result<(f64, f64), String> {
+    match value.split_once('+') {
+        Some((time, increment)) => Ok((parse_timer(time)?, parse_timer(increment)?)),
+        None => Ok((parse_timer(value)?, 0.0)),
+    }
+}
+
+/// A clock for a two player game. Only one side counts down at a time, and
+/// ending a move stops that side's clock and starts the other's.
+pub struct ChessClock {
+    sides: [TimerState; 2],
+    increment: f64,
+    turn: Option<usize>,
+}
+
+impl ChessClock {
+    /// Both clocks start out stopped until the first move is started
+    pub fn new(time: f64, increment: f64, clock: &impl Clock) -> ChessClock {
+        let side = || {
+            let mut side = TimerState::new(TimerMode::Default, time, clock);
+            side.set_paused(true, clock);
+            side
+        };
+
+        ChessClock {
+            sides: [side(), side()],
+            increment,
+            turn: None,
+        }
+    }
+
+    /// End the current move, adding the increment to the side that just moved, and start
+    /// the other side's clock. The first switch starts the first side. Once either side
+    /// has run out of time the clocks stay as they are.
+    pub fn switch(&mut self, clock: &impl Clock) {
+        if self.flagged().is_some() {
+            return;
+        }
+
+        let next = match self.turn {
+            Some(turn) => {
+                let side = &mut self.sides[turn];
+                side.set_paused(true, clock);
+                side.timer += self.increment;
+                1 - turn
+            }
+            None => 0,
+        };
+
+        self.sides[next].set_paused(false, clock);
+        self.turn = Some(next);
+    }
+
+    pub fn update(&mut self, clock: &impl Clock) {
+        for side in self.sides.iter_mut() {
+            side.update(clock);
+        }
+    }
+
+    /// The side that ran out of time, if either has
+    pub fn flagged(&self) -> Option<usize> {
+        self.sides.iter().position(|side| !side.is_active())
+    }
+
+    /// Each side's time as of right now, along with whether it's that side's turn
+    pub fn times<'a>(&'a self, clock: &'a impl Clock) -> impl Iterator<Item = (f64, bool)> + 'a {
+        self.sides
+            .iter()
+            .enumerate()
+            .map(move |(i, side)| (side.display_time(clock), self.turn == Some(i)))
+    }
+}
+
+#[test]
+fn it_should_parse_the_time_and_increment() {
+    assert_eq!(Ok((300.0, 0.0)), parse_chess("5m"));
+    assert_eq!(Ok((300.0, 3.0)), parse_chess("5m+3s"));
+    assert_eq!(Ok((300.0, 2.0)), parse_chess("05:00+2"));
+    assert!(parse_chess("5m+").is_err());
+}
+
+#[test]
+fn it_should_only_run_the_clock_of_the_side_to_move() {
+    let clock = FakeClock::new();
+    let mut chess = ChessClock::new(300.0, 0.0, &clock);
+
+    chess.switch(&clock);
+    clock.advance(10.0);
+    chess.switch(&clock);
+    clock.advance(4.0);
+    chess.update(&clock);
+
+    assert_eq!(
+        vec![(290.0, false), (296.0, true)],
+        chess.times(&clock).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_should_add_the_increment_to_the_side_that_moved() {
+    let clock = FakeClock::new();
+    let mut chess = ChessClock::new(300.0, 3.0, &clock);
+
+    chess.switch(&clock);
+    clock.advance(10.0);
+    chess.switch(&clock);
+
+    assert_eq!(
+        vec![(293.0, false), (300.0, true)],
+        chess.times(&clock).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_should_flag_the_side_that_runs_out_of_time() {
+    let clock = FakeClock::new();
+    let mut chess = ChessClock::new(60.0, 5.0, &clock);
+
+    chess.switch(&clock);
+    chess.switch(&clock);
+    clock.advance(61.0);
+    chess.update(&clock);
+    assert_eq!(Some(1), chess.flagged());
+
+    // Nothing moves once a side has flagged
+    chess.switch(&clock);
+    assert_eq!(
+        vec![(65.0, false), (0.0, true)],
+        chess.times(&clock).collect::<Vec<_>>()
+    );
+}
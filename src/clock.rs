@@ -0,0 +1,45 @@
+use std::time::Instant;
+
+#[cfg(test)]
+use std::{cell::Cell, time::Duration};
+
+/// The source of time for the timer. Everything that measures elapsed time
+/// goes through this so tests can control exactly how much time passes.
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when it's told to
+#[cfg(test)]
+pub struct FakeClock {
+    now: Cell<Instant>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn new() -> FakeClock {
+        FakeClock {
+            now: Cell::new(Instant::now()),
+        }
+    }
+
+    pub fn advance(&self, seconds: f64) {
+        self.now
+            .set(self.now.get() + Duration::from_secs_f64(seconds));
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
@@ -0,0 +1,145 @@
+use sdl2::pixels::Color;
+
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("black", Color::RGB(0, 0, 0)),
+    ("white", Color::RGB(255, 255, 255)),
+    ("gray", Color::RGB(128, 128, 128)),
+    ("grey", Color::RGB(128, 128, 128)),
+    ("silver", Color::RGB(192, 192, 192)),
+    ("red", Color::RGB(255, 0, 0)),
+    ("maroon", Color::RGB(128, 0, 0)),
+    ("orange", Color::RGB(255, 165, 0)),
+    ("yellow", Color::RGB(255, 255, 0)),
+    ("olive", Color::RGB(128, 128, 0)),
+    ("lime", Color::RGB(0, 255, 0)),
+    ("green", Color::RGB(0, 128, 0)),
+    ("teal", Color::RGB(0, 128, 128)),
+    ("cyan", Color::RGB(0, 255, 255)),
+    ("aqua", Color::RGB(0, 255, 255)),
+    ("blue", Color::RGB(0, 0, 255)),
+    ("navy", Color::RGB(0, 0, 128)),
+    ("purple", Color::RGB(128, 0, 128)),
+    ("magenta", Color::RGB(255, 0, 255)),
+    ("fuchsia", Color::RGB(255, 0, 255)),
+    ("pink", Color::RGB(255, 192, 203)),
+    ("brown", Color::RGB(165, 42, 42)),
+];
+
+/// Parse a color given as a CSS color name or as hex in one of the
+/// forms `RGB`, `RRGGBB`, or `RRGGBBAA` (each with an optional `#`).
+pub fn parse_color(value: &str) -> Result<Color, String> {
+    let invalid = || format!("Invalid color: \"{}\"", value);
+
+    if let Some((_, color)) = NAMED_COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(value))
+    {
+        return Ok(*color);
+    }
+
+    let hex = value.strip_prefix('#').unwrap_or(value);
+
+    // Check every character up front. Besides rejecting bad digits, this
+    // guarantees the string is ASCII so slicing by byte offset is safe.
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let channel = |i: usize, width: usize| {
+        let value = u8::from_str_radix(&hex[i * width..(i + 1) * width], 16).unwrap();
+        match width {
+            // Shorthand digits are doubled up, so "f" becomes "ff".
+            1 => value * 0x11,
+            _ => value,
+        }
+    };
+
+    match hex.len() {
+        3 => Ok(Color::RGB(channel(0, 1), channel(1, 1), channel(2, 1))),
+        6 => Ok(Color::RGB(channel(0, 2), channel(1, 2), channel(2, 2))),
+        8 => Ok(Color::RGBA(
+            channel(0, 2),
+            channel(1, 2),
+            channel(2, 2),
+            channel(3, 2),
+        )),
+        _ => Err(invalid()),
+    }
+}
+
+/// Draw `color` over `background`, using the alpha of `color` to decide how much
+/// of the background shows through. The result is always fully opaque.
+pub fn blend(background: Color, color: Color) -> Color {
+    let alpha = color.a as f32 / 255.0;
+    let mix =
+        |under: u8, over: u8| (under as f32 + (over as f32 - under as f32) * alpha).round() as u8;
+
+    Color::RGB(
+        mix(background.r, color.r),
+        mix(background.g, color.g),
+        mix(background.b, color.b),
+    )
+}
+
+#[test]
+fn it_should_parse_a_hex_color_with_a_hash() {
+    assert_eq!(Color::RGB(255, 128, 0), parse_color("#ff8000").unwrap());
+}
+
+#[test]
+fn it_should_parse_a_hex_color_without_a_hash() {
+    assert_eq!(Color::RGB(18, 52, 86), parse_color("123456").unwrap());
+}
+
+#[test]
+fn it_should_parse_a_shorthand_hex_color() {
+    assert_eq!(Color::RGB(255, 0, 170), parse_color("#f0a").unwrap());
+}
+
+#[test]
+fn it_should_parse_a_hex_color_with_alpha() {
+    assert_eq!(Color::RGBA(0, 0, 0, 128), parse_color("00000080").unwrap());
+    assert_eq!(
+        Color::RGBA(255, 255, 255, 0),
+        parse_color("#FFFFFF00").unwrap()
+    );
+}
+
+#[test]
+fn it_should_parse_a_named_color() {
+    assert_eq!(Color::RGB(255, 0, 0), parse_color("red").unwrap());
+    assert_eq!(Color::RGB(0, 0, 128), parse_color("Navy").unwrap());
+}
+
+#[test]
+fn it_should_reject_malformed_colors() {
+    assert!(parse_color("").is_err());
+    assert!(parse_color("#").is_err());
+    assert!(parse_color("#ff80").is_err());
+    assert!(parse_color("#ff800").is_err());
+    assert!(parse_color("#gg0000").is_err());
+    assert!(parse_color("not-a-color").is_err());
+    assert!(parse_color("##ff8000").is_err());
+    assert!(parse_color("+f8000").is_err());
+}
+
+#[test]
+fn it_should_reject_non_ascii_colors_without_panicking() {
+    assert!(parse_color("ff€0").is_err());
+    assert!(parse_color("#ééé").is_err());
+}
+
+#[test]
+fn it_should_blend_a_color_over_the_background_using_its_alpha() {
+    let background = Color::RGB(0, 0, 0);
+
+    assert_eq!(
+        Color::RGB(255, 0, 0),
+        blend(background, Color::RGB(255, 0, 0))
+    );
+    assert_eq!(
+        Color::RGB(128, 0, 0),
+        blend(background, Color::RGBA(255, 0, 0, 128))
+    );
+    assert_eq!(background, blend(background, Color::RGBA(255, 0, 0, 0)));
+}
@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use crate::parse_duration;
+use crate::state::crossed;
+
+// How long a cue stays on screen once the countdown reaches it
+const CUE_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cue {
+    pub at: f64,
+    pub text: String,
+}
+
+/// Parse a cue like `2m:Switch sides`. Since the time can have colons of its
+/// own (e.g. `1:30:Final push`), it's the longest part before a colon that
+/// reads as a time.
+pub fn parse_cue(value: &str) -> Result<Cue, String> {
+    value
+        .rmatch_indices(':')
+        .find_map(|(i, _)| {
+            let at = parse_duration(value[..i].trim()).ok()?;
+            Some(Cue {
+                at,
+                text: value[i + 1..].trim().to_string(),
+            })
+        })
+        .filter(|cue| !cue.text.is_empty())
+        .ok_or(format!("Invalid cue: \"{}\"", value))
+}
+
+/// The cues for the countdown, and the one currently on screen
+pub struct Cues {
+    cues: Vec<Cue>,
+    shown: Option<(usize, Instant)>,
+}
+
+impl Cues {
+    pub fn new(mut cues: Vec<Cue>) -> Cues {
+        // The countdown reaches the latest times first
+        cues.sort_by(|a, b| b.at.total_cmp(&a.at));
+        Cues { cues, shown: None }
+    }
+
+    /// Show the last cue the countdown passed on its way from `previous` to `current`
+    pub fn update(&mut self, previous: f64, current: f64, now: Instant) {
+        if let Some(i) = self
+            .cues
+            .iter()
+            .rposition(|cue| crossed(previous, current, cue.at))
+        {
+            self.shown = Some((i, now));
+        }
+    }
+
+    /// The text of the cue to show, until it's been up for a few seconds
+    pub fn current(&self, now: Instant) -> Option<&str> {
+        self.shown
+            .filter(|(_, shown_at)| now.duration_since(*shown_at) < CUE_DURATION)
+            .map(|(i, _)| self.cues[i].text.as_str())
+    }
+}
+
+#[test]
+fn it_should_parse_a_cue() {
+    assert_eq!(
+        Ok(Cue {
+            at: 120.0,
+            text: "Switch sides".to_string()
+        }),
+        parse_cue("2m:Switch sides")
+    );
+    assert_eq!(
+        Ok(Cue {
+            at: 90.0,
+            text: "Final push: go".to_string()
+        }),
+        parse_cue("1:30:Final push: go")
+    );
+    assert!(parse_cue("Switch sides").is_err());
+    assert!(parse_cue("2m:").is_err());
+}
+
+#[test]
+fn it_should_keep_every_colon_in_the_text_of_a_cue() {
+    assert_eq!(
+        Ok(Cue {
+            at: 90.0,
+            text: "Step 1: a: b".to_string()
+        }),
+        parse_cue("1:30:Step 1: a: b")
+    );
+}
+
+#[test]
+fn it_should_show_a_cue_once_the_countdown_crosses_it() {
+    let start = Instant::now();
+    let mut cues = Cues::new(vec![
+        parse_cue("30s:Final push").unwrap(),
+        parse_cue("2m:Switch sides").unwrap(),
+    ]);
+
+    cues.update(121.0, 120.5, start);
+    assert_eq!(None, cues.current(start));
+
+    cues.update(120.5, 119.9, start);
+    assert_eq!(Some("Switch sides"), cues.current(start));
+    assert_eq!(None, cues.current(start + CUE_DURATION));
+
+    // Jumping past both at once shows the later one
+    let mut cues = Cues::new(vec![
+        parse_cue("2m:Switch sides").unwrap(),
+        parse_cue("30s:Final push").unwrap(),
+    ]);
+    cues.update(200.0, 10.0, start);
+    assert_eq!(Some("Final push"), cues.current(start));
+}
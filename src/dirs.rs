@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+/// The directory the timer keeps its state in between launches, following
+/// the XDG base directory spec (`$XDG_STATE_HOME/timer` or `~/.local/state/timer`).
+pub fn state_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .map(|dir| dir.join("timer"))
+}
+
+/// The directory for lock files, which is cleared out when the user logs out
+/// (`$XDG_RUNTIME_DIR`), or the temporary directory if there isn't one
+pub fn runtime_dir() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+}
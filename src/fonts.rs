@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+/// The directories fonts are usually installed to on this platform
+fn font_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.extend(home.map(|home| home.join("Library/Fonts")));
+    } else if cfg!(windows) {
+        let windir = std::env::var_os("WINDIR").unwrap_or("C:\\Windows".into());
+        dirs.push(PathBuf::from(windir).join("Fonts"));
+        dirs.extend(
+            std::env::var_os("LOCALAPPDATA")
+                .map(|local| PathBuf::from(local).join("Microsoft\\Windows\\Fonts")),
+        );
+    } else {
+        dirs.push(PathBuf::from("/usr/share/fonts"));
+        dirs.push(PathBuf::from("/usr/local/share/fonts"));
+
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+        dirs.extend(data_home.map(|dir| dir.join("fonts")));
+        dirs.extend(home.map(|home| home.join(".fonts")));
+    }
+
+    dirs
+}
+
+/// Whether the file is a font that SDL_ttf can load
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf")
+        })
+}
+
+/// Collect the font files under `dir`, looking through its subdirectories too
+fn find_fonts(dir: &Path, fonts: &mut Vec<PathBuf>) {
+    // A font directory that doesn't exist or can't be read just has no fonts
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            find_fonts(&path, fonts);
+        } else if is_font_file(&path) {
+            fonts.push(path);
+        }
+    }
+}
+
+/// Every font file installed in the usual places, sorted by path
+pub fn list_fonts() -> Vec<PathBuf> {
+    let mut fonts = Vec::new();
+    for dir in font_dirs() {
+        find_fonts(&dir, &mut fonts);
+    }
+
+    fonts.sort();
+    fonts.dedup();
+    fonts
+}
+
+#[test]
+fn it_should_only_pick_out_font_files() {
+    assert!(is_font_file(Path::new(
+        "/usr/share/fonts/Roboto-Regular.ttf"
+    )));
+    assert!(is_font_file(Path::new("DejaVuSans.OTF")));
+    assert!(!is_font_file(Path::new("/usr/share/fonts/fonts.dir")));
+    assert!(!is_font_file(Path::new("Roboto-Regular.woff2")));
+    assert!(!is_font_file(Path::new("ttf")));
+}
+
+#[test]
+fn it_should_find_fonts_in_subdirectories() {
+    let dir = std::env::temp_dir().join(format!("timer-fonts-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("truetype")).unwrap();
+    std::fs::write(dir.join("truetype/Roboto.ttf"), "").unwrap();
+    std::fs::write(dir.join("README"), "").unwrap();
+
+    let mut fonts = Vec::new();
+    find_fonts(&dir, &mut fonts);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(vec![dir.join("truetype/Roboto.ttf")], fonts);
+}
@@ -0,0 +1,246 @@
+use crate::dirs::state_dir;
+
+use sdl2::rect::Rect;
+
+use std::fmt;
+use std::str::FromStr;
+
+const GEOMETRY_FILE: &str = "geometry";
+// How much of the display a tiled window takes up. A strip along an edge is the full length
+// of that edge, and a window in a corner takes up a fraction of the display both ways.
+const TILE_STRIP_HEIGHT: f32 = 0.08;
+const TILE_STRIP_WIDTH: f32 = 0.12;
+const TILE_CORNER_WIDTH: f32 = 0.2;
+const TILE_CORNER_HEIGHT: f32 = 0.1;
+
+/// The edge or corner of the display to tile the window against
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+pub fn parse_edge(value: &str) -> Result<Edge, String> {
+    match value {
+        "top" => Ok(Edge::Top),
+        "bottom" => Ok(Edge::Bottom),
+        "left" => Ok(Edge::Left),
+        "right" => Ok(Edge::Right),
+        "top-left" => Ok(Edge::TopLeft),
+        "top-right" => Ok(Edge::TopRight),
+        "bottom-left" => Ok(Edge::BottomLeft),
+        "bottom-right" => Ok(Edge::BottomRight),
+        _ => Err(format!("Invalid tile: unknown edge \"{}\"", value)),
+    }
+}
+
+/// Where the window was and how big it was when the timer last quit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Geometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Geometry {
+    /// Keep the window on one of the displays. A window that was left on a
+    /// monitor that's no longer connected is moved onto the primary display,
+    /// which is the first of `displays`, and shrunk to fit if it has to be.
+    pub fn clamp_to_displays(self, displays: &[Rect]) -> Geometry {
+        let on_screen = displays
+            .iter()
+            .any(|display| display.contains_point((self.x, self.y)));
+
+        match displays.first() {
+            Some(primary) if !on_screen => {
+                let width = self.width.min(primary.width());
+                let height = self.height.min(primary.height());
+
+                Geometry {
+                    x: self.x.clamp(primary.left(), primary.right() - width as i32),
+                    y: self
+                        .y
+                        .clamp(primary.top(), primary.bottom() - height as i32),
+                    width,
+                    height,
+                }
+            }
+            _ => self,
+        }
+    }
+}
+
+/// Where a window tiled against `edge` of a display with the given bounds goes
+pub fn tile(edge: Edge, bounds: Rect) -> Geometry {
+    let (display_width, display_height) = (bounds.width() as f32, bounds.height() as f32);
+    let (width, height) = match edge {
+        Edge::Top | Edge::Bottom => (display_width, display_height * TILE_STRIP_HEIGHT),
+        Edge::Left | Edge::Right => (display_width * TILE_STRIP_WIDTH, display_height),
+        _ => (
+            display_width * TILE_CORNER_WIDTH,
+            display_height * TILE_CORNER_HEIGHT,
+        ),
+    };
+    let (width, height) = (width as u32, height as u32);
+
+    let x = match edge {
+        Edge::Right | Edge::TopRight | Edge::BottomRight => bounds.right() - width as i32,
+        _ => bounds.left(),
+    };
+    let y = match edge {
+        Edge::Bottom | Edge::BottomLeft | Edge::BottomRight => bounds.bottom() - height as i32,
+        _ => bounds.top(),
+    };
+
+    Geometry {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+impl fmt::Display for Geometry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {} {} {}", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl FromStr for Geometry {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Geometry, String> {
+        let invalid = || format!("Invalid geometry: \"{}\"", value.trim());
+        let parts: Vec<&str> = value.split_whitespace().collect();
+
+        match parts[..] {
+            [x, y, width, height] => Ok(Geometry {
+                x: x.parse().map_err(|_| invalid())?,
+                y: y.parse().map_err(|_| invalid())?,
+                width: width.parse().map_err(|_| invalid())?,
+                height: height.parse().map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Read the geometry saved by the last run, if there is one
+pub fn load() -> Option<Geometry> {
+    let path = state_dir()?.join(GEOMETRY_FILE);
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    contents
+        .parse()
+        .map_err(|e| crate::warn(format!("ignoring the saved window geometry: {}", e)))
+        .ok()
+}
+
+pub fn save(geometry: Geometry) -> Result<(), String> {
+    let dir = state_dir().ok_or("Could not find a directory to save the window geometry in")?;
+
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(GEOMETRY_FILE), geometry.to_string()))
+        .map_err(|e| format!("Could not save the window geometry: {}", e))
+}
+
+#[test]
+fn it_should_read_back_a_saved_geometry() {
+    let geometry = Geometry {
+        x: -1280,
+        y: 40,
+        width: 800,
+        height: 600,
+    };
+
+    assert_eq!(Ok(geometry), geometry.to_string().parse());
+}
+
+#[test]
+fn it_should_reject_a_malformed_geometry() {
+    assert!("".parse::<Geometry>().is_err());
+    assert!("10 20 800".parse::<Geometry>().is_err());
+    assert!("10 20 800 -600".parse::<Geometry>().is_err());
+    assert!("a b c d".parse::<Geometry>().is_err());
+}
+
+#[test]
+fn it_should_keep_a_window_that_is_on_a_display() {
+    let displays = [Rect::new(0, 0, 1920, 1080), Rect::new(1920, 0, 1280, 1024)];
+    let geometry = Geometry {
+        x: 2000,
+        y: 100,
+        width: 800,
+        height: 600,
+    };
+
+    assert_eq!(geometry, geometry.clamp_to_displays(&displays));
+}
+
+#[test]
+fn it_should_move_a_window_from_a_missing_display_onto_the_primary_one() {
+    let displays = [Rect::new(0, 0, 1280, 720)];
+    let geometry = Geometry {
+        x: 2000,
+        y: 100,
+        width: 1600,
+        height: 600,
+    };
+
+    assert_eq!(
+        Geometry {
+            x: 0,
+            y: 100,
+            width: 1280,
+            height: 600,
+        },
+        geometry.clamp_to_displays(&displays)
+    );
+}
+
+#[test]
+fn it_should_tile_a_strip_along_an_edge() {
+    let bounds = Rect::new(0, 0, 1000, 500);
+
+    assert_eq!(
+        Geometry {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 40,
+        },
+        tile(Edge::Top, bounds)
+    );
+    assert_eq!(
+        Geometry {
+            x: 880,
+            y: 0,
+            width: 120,
+            height: 500,
+        },
+        tile(Edge::Right, bounds)
+    );
+}
+
+#[test]
+fn it_should_tile_a_window_into_a_corner_of_its_display() {
+    let bounds = Rect::new(1920, 0, 1000, 500);
+
+    assert_eq!(
+        Geometry {
+            x: 2720,
+            y: 450,
+            width: 200,
+            height: 50,
+        },
+        tile(Edge::BottomRight, bounds)
+    );
+    assert!(parse_edge("middle").is_err());
+}
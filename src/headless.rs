@@ -0,0 +1,126 @@
+use crate::clock::SystemClock;
+use crate::state::{TimerMode, TimerState};
+use crate::time_file::TimeFile;
+use crate::{format_timer, progress_fraction, TimeFormat};
+
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use terminal_size::{terminal_size, Width};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(50);
+const MIN_BAR_WIDTH: usize = 10;
+
+/// Draw a progress bar with `width` cells, filling in the fraction that's complete
+pub fn render_progress_bar(fraction: f64, width: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// Catch Ctrl-C so that the timer can stop at the end of the line it's drawing,
+/// rather than leave it half finished. A second Ctrl-C quits straight away.
+#[cfg(unix)]
+pub fn catch_interrupt() -> Arc<AtomicBool> {
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = interrupted.clone();
+
+    let handler = move || {
+        if flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(crate::EXIT_CANCELLED.into());
+        }
+    };
+    if let Err(e) = ctrlc::set_handler(handler) {
+        crate::warn(format!("could not catch Ctrl-C: {}", e));
+    }
+
+    interrupted
+}
+
+#[cfg(not(unix))]
+pub fn catch_interrupt() -> Arc<AtomicBool> {
+    Arc::new(AtomicBool::new(false))
+}
+
+/// Run the timer in the terminal instead of a window. On a terminal the time and a
+/// progress bar are redrawn in place; otherwise a plain line is printed each second.
+/// It runs until the timer finishes, or until `interrupted` is set by Ctrl-C.
+pub fn run(
+    state: &mut TimerState,
+    clock: &SystemClock,
+    time_format: TimeFormat,
+    mut time_file: Option<&mut TimeFile>,
+    interrupted: &AtomicBool,
+) {
+    let mut stdout = std::io::stdout();
+    let is_terminal = stdout.is_terminal();
+    let mut last_second = None;
+
+    loop {
+        state.update(clock);
+        let formatted_timer = format_timer(state.display_time(clock), time_format);
+
+        if let Some(time_file) = time_file.as_mut() {
+            time_file.write(&formatted_timer, !state.is_active());
+        }
+
+        if is_terminal {
+            let line = match state.mode {
+                TimerMode::Default => {
+                    let columns = terminal_size().map_or(80, |(Width(width), _)| width as usize);
+                    let bar_width = columns
+                        .saturating_sub(formatted_timer.len() + 4)
+                        .max(MIN_BAR_WIDTH);
+                    format!(
+                        "{} {}",
+                        render_progress_bar(
+                            progress_fraction(state.timer, state.initial),
+                            bar_width
+                        ),
+                        formatted_timer
+                    )
+                }
+                TimerMode::Stopwatch => formatted_timer,
+            };
+
+            print!("\r{}", line);
+            let _ = stdout.flush();
+        } else {
+            let second = state.timer.ceil() as i64;
+            if last_second != Some(second) {
+                println!("{}", formatted_timer);
+                last_second = Some(second);
+            }
+        }
+
+        if !state.is_active() || interrupted.load(Ordering::SeqCst) {
+            break;
+        }
+
+        thread::sleep(REFRESH_INTERVAL);
+    }
+
+    if is_terminal {
+        println!();
+    }
+}
+
+#[test]
+fn it_should_render_an_empty_progress_bar() {
+    assert_eq!("[----------]", render_progress_bar(0.0, 10));
+}
+
+#[test]
+fn it_should_render_a_partly_filled_progress_bar() {
+    assert_eq!("[#####-----]", render_progress_bar(0.5, 10));
+    assert_eq!("[##--]", render_progress_bar(0.4, 4));
+}
+
+#[test]
+fn it_should_render_a_full_progress_bar() {
+    assert_eq!("[##########]", render_progress_bar(1.0, 10));
+    assert_eq!("[##########]", render_progress_bar(1.5, 10));
+}
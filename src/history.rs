@@ -0,0 +1,71 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::state::TimerMode;
+
+const HEADER: &str = "start,seconds,label,mode\n";
+
+/// A line of the history file for a timer that ran for `seconds`
+pub fn format_record(start: &str, seconds: f64, label: &str, mode: TimerMode) -> String {
+    let mode = match mode {
+        TimerMode::Default => "countdown",
+        TimerMode::Stopwatch => "stopwatch",
+    };
+
+    format!("{},{:.2},{},{}\n", start, seconds, escape(label), mode)
+}
+
+/// Quote a field that would otherwise break up the line, doubling any quotes inside it
+fn escape(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        _ => field.to_string(),
+    }
+}
+
+/// Add a record to the end of the history file, starting it with a header if it's new.
+/// Everything goes out in a single append, so that several timers finishing at once
+/// don't write over each other.
+pub fn append(path: &str, record: &str) -> Result<(), String> {
+    let write = || -> std::io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let contents = match file.metadata()?.len() {
+            0 => format!("{}{}", HEADER, record),
+            _ => record.to_string(),
+        };
+
+        file.write_all(contents.as_bytes())
+    };
+
+    write().map_err(|e| format!("Could not write to the history file \"{}\": {}", path, e))
+}
+
+/// Add a record for a timer that ran for `seconds` to the history file, warning
+/// rather than stopping the timer if it can't be written
+pub fn record(path: &str, start: &str, seconds: f64, label: &str, mode: TimerMode) {
+    if let Err(e) = append(path, &format_record(start, seconds, label, mode)) {
+        crate::warn(e);
+    }
+}
+
+#[test]
+fn it_should_format_a_record() {
+    assert_eq!(
+        "2024-05-01T09:00:00+01:00,1500.00,Focus,countdown\n",
+        format_record(
+            "2024-05-01T09:00:00+01:00",
+            1500.0,
+            "Focus",
+            TimerMode::Default
+        )
+    );
+    assert_eq!(
+        "2024-05-01T09:00:00+01:00,61.25,\"Set 1, \"\"warm up\"\"\",stopwatch\n",
+        format_record(
+            "2024-05-01T09:00:00+01:00",
+            61.25,
+            "Set 1, \"warm up\"",
+            TimerMode::Stopwatch
+        )
+    );
+}
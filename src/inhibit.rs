@@ -0,0 +1,65 @@
+#[cfg(all(unix, not(target_os = "macos")))]
+use dbus::{ffidisp::Connection, Message};
+
+/// Asks the desktop to keep the system awake for as long as this is held.
+/// The inhibition is released when it's dropped.
+pub struct SleepInhibitor {
+    // The screen saver ties the inhibition to our D-Bus connection,
+    // so it has to stay open for as long as we want to stay awake.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    connection: Connection,
+    #[cfg(all(unix, not(target_os = "macos")))]
+    cookie: u32,
+}
+
+impl SleepInhibitor {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn acquire() -> Result<SleepInhibitor, String> {
+        let connection =
+            Connection::get_private(dbus::ffidisp::BusType::Session).map_err(|e| e.to_string())?;
+
+        let message = Message::new_method_call(
+            "org.freedesktop.ScreenSaver",
+            "/org/freedesktop/ScreenSaver",
+            "org.freedesktop.ScreenSaver",
+            "Inhibit",
+        )?
+        .append2("timer", "A timer is running");
+
+        let reply = connection
+            .send_with_reply_and_block(message, 2000)
+            .map_err(|e| format!("Could not inhibit sleep: {}", e))?;
+        let cookie = reply
+            .get1::<u32>()
+            .ok_or("Could not inhibit sleep: the screen saver did not return a cookie")?;
+
+        Ok(SleepInhibitor { connection, cookie })
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    pub fn acquire() -> Result<SleepInhibitor, String> {
+        Err("Inhibiting sleep is only supported on XDG desktops".to_string())
+    }
+}
+
+impl Drop for SleepInhibitor {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn drop(&mut self) {
+        if let Ok(message) = Message::new_method_call(
+            "org.freedesktop.ScreenSaver",
+            "/org/freedesktop/ScreenSaver",
+            "org.freedesktop.ScreenSaver",
+            "UnInhibit",
+        ) {
+            let _ = self.connection.send(message.append1(self.cookie));
+        }
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    fn drop(&mut self) {}
+}
+
+/// Try to inhibit sleep, warning rather than failing if the desktop won't allow it.
+pub fn inhibit_sleep() -> Option<SleepInhibitor> {
+    SleepInhibitor::acquire().map_err(crate::warn).ok()
+}
@@ -0,0 +1,113 @@
+use sdl2::keyboard::Keycode;
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Pause,
+    Quit,
+    Lap,
+    CycleTheme,
+    QueueAgain,
+}
+
+fn parse_action(name: &str) -> Result<Action, String> {
+    match name {
+        "pause" => Ok(Action::Pause),
+        "quit" => Ok(Action::Quit),
+        "lap" => Ok(Action::Lap),
+        "theme" => Ok(Action::CycleTheme),
+        "again" => Ok(Action::QueueAgain),
+        _ => Err(format!("Invalid keys: unknown action \"{}\"", name)),
+    }
+}
+
+pub fn parse_keycode(name: &str) -> Result<Keycode, String> {
+    Keycode::from_name(name).ok_or(format!("Invalid keys: unknown key name \"{}\"", name))
+}
+
+pub fn default_key_bindings() -> HashMap<Keycode, Action> {
+    HashMap::from([
+        (Keycode::Space, Action::Pause),
+        (Keycode::Escape, Action::Quit),
+        (Keycode::L, Action::Lap),
+        (Keycode::T, Action::CycleTheme),
+        (Keycode::N, Action::QueueAgain),
+    ])
+}
+
+/// Parse a comma separated list of `action=Key` bindings (e.g. "pause=P,quit=Q").
+/// Any action that's rebound loses its default key, and binding a single key
+/// to more than one action is an error.
+pub fn parse_key_bindings(value: &str) -> Result<HashMap<Keycode, Action>, String> {
+    let mut rebound = HashMap::new();
+
+    for binding in value.split(',') {
+        let (action, key) = binding.split_once('=').ok_or(format!(
+            "Invalid keys: expected action=Key but got \"{}\"",
+            binding
+        ))?;
+        let action = parse_action(action.trim())?;
+        let keycode = parse_keycode(key.trim())?;
+
+        if let Some(existing) = rebound.insert(keycode, action) {
+            if existing != action {
+                return Err(format!(
+                    "Invalid keys: \"{}\" is bound more than once",
+                    key.trim()
+                ));
+            }
+        }
+    }
+
+    let mut key_bindings = default_key_bindings();
+    key_bindings.retain(|keycode, action| {
+        !rebound.contains_key(keycode) && !rebound.values().any(|a| a == action)
+    });
+    key_bindings.extend(rebound);
+
+    Ok(key_bindings)
+}
+
+#[test]
+fn it_should_parse_key_names() {
+    assert_eq!(Keycode::Space, parse_keycode("Space").unwrap());
+    assert_eq!(Keycode::Q, parse_keycode("Q").unwrap());
+}
+
+#[test]
+fn it_should_reject_unknown_key_names() {
+    assert!(parse_keycode("NotAKey").is_err());
+    assert!(parse_key_bindings("pause=NotAKey").is_err());
+}
+
+#[test]
+fn it_should_reject_unknown_actions() {
+    assert!(parse_key_bindings("explode=Space").is_err());
+}
+
+#[test]
+fn it_should_replace_the_default_key_for_a_rebound_action() {
+    let key_bindings = parse_key_bindings("pause=P").unwrap();
+
+    assert_eq!(Some(&Action::Pause), key_bindings.get(&Keycode::P));
+    assert_eq!(None, key_bindings.get(&Keycode::Space));
+    assert_eq!(Some(&Action::Quit), key_bindings.get(&Keycode::Escape));
+}
+
+#[test]
+fn it_should_reject_conflicting_key_bindings() {
+    assert!(parse_key_bindings("pause=Q,quit=Q").is_err());
+}
+
+#[test]
+fn it_should_queue_another_run_with_n_by_default() {
+    assert_eq!(
+        Some(&Action::QueueAgain),
+        default_key_bindings().get(&Keycode::N)
+    );
+    assert_eq!(
+        Some(&Action::QueueAgain),
+        parse_key_bindings("again=A").unwrap().get(&Keycode::A)
+    );
+}
@@ -0,0 +1,76 @@
+use crate::{format_timer, TimeFormat};
+
+/// The laps recorded while the timer runs, kept as the elapsed time
+/// at the moment each one was recorded.
+pub struct Laps {
+    splits: Vec<f64>,
+}
+
+impl Laps {
+    pub fn new() -> Laps {
+        Laps { splits: Vec::new() }
+    }
+
+    pub fn record(&mut self, elapsed: f64) {
+        self.splits.push(elapsed);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.splits.is_empty()
+    }
+
+    /// Each lap as its number, how long the lap took, and the total time at its end
+    pub fn laps(&self) -> impl DoubleEndedIterator<Item = (usize, f64, f64)> + '_ {
+        self.splits.iter().enumerate().map(|(i, split)| {
+            let start = match i {
+                0 => 0.0,
+                _ => self.splits[i - 1],
+            };
+
+            (i + 1, split - start, *split)
+        })
+    }
+
+    /// One line per lap, for saving the laps once the timer quits
+    pub fn to_lines(&self, time_format: TimeFormat) -> String {
+        self.laps()
+            .map(|(number, lap, total)| {
+                format!("{}\n", format_lap(number, lap, total, time_format))
+            })
+            .collect()
+    }
+}
+
+pub fn format_lap(number: usize, lap: f64, total: f64, time_format: TimeFormat) -> String {
+    format!(
+        "Lap {}  {}  {}",
+        number,
+        format_timer(lap, time_format),
+        format_timer(total, time_format)
+    )
+}
+
+#[test]
+fn it_should_time_each_lap_from_the_end_of_the_last_one() {
+    let mut laps = Laps::new();
+    laps.record(10.0);
+    laps.record(25.5);
+    laps.record(30.0);
+
+    assert_eq!(
+        vec![(1, 10.0, 10.0), (2, 15.5, 25.5), (3, 4.5, 30.0)],
+        laps.laps().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_should_write_a_line_per_lap() {
+    let mut laps = Laps::new();
+    laps.record(10.0);
+    laps.record(75.0);
+
+    assert_eq!(
+        "Lap 1  00:00:10.00  00:00:10.00\nLap 2  00:01:05.00  00:01:15.00\n",
+        laps.to_lines(TimeFormat::default())
+    );
+}
@@ -1,29 +1,106 @@
 extern crate sdl2;
 
-#[cfg(all(unix, not(target_os = "macos")))]
-use dbus::{
-    arg::messageitem::{MessageItem, MessageItemArray},
-    ffidisp::Connection,
-    Message,
-};
+mod audio;
+mod chess;
+mod clock;
+mod color;
+mod cues;
+mod dirs;
+mod fonts;
+mod geometry;
+mod headless;
+mod history;
+mod inhibit;
+mod keys;
+mod laps;
+mod notification;
+mod once;
+mod options;
+mod resume;
+mod roll;
+mod rpc;
+mod segments;
+mod speech;
+mod state;
+mod stopwatches;
+mod theme;
+mod time_file;
 
+use chrono::Timelike;
 use sdl2::{
     event::{Event, WindowEvent},
+    image::LoadTexture,
     keyboard::Keycode,
     pixels::Color,
     rect::Rect,
+    render::{BlendMode, Texture, TextureCreator},
     rwops::RWops,
     ttf,
+    video::WindowContext,
 };
 
+use clock::SystemClock;
+use keys::Action;
+use options::Options;
+use state::{TimerMode, TimerState};
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+const FONT_BYTES: &[u8] = include_bytes!("../assets/Roboto-Regular.ttf");
+const FONT_POINT_SIZE: u16 = 512;
+// The outline's width as a fraction of the point size, so it keeps the same weight at any size
+const HOLLOW_OUTLINE_FRACTION: f32 = 0.02;
 const WIDTH: u32 = 800;
 const HEIGHT: u32 = 600;
 const VELOCITY_SPEED: i32 = 1;
-const TEXT_PADDING: f32 = 0.1;
-const TEXT_SIZE: f32 = 0.8;
 const DVD_FONT_SCALE: f32 = 0.25;
+const DVD_ELAPSED_PADDING: f32 = 0.02;
+const DVD_ELAPSED_SCALE: f32 = 0.1;
+const SMOOTH_RESIZE_SPEED: f32 = 12.0;
+const LABEL_SIZE: f32 = 0.08;
+const CUE_SIZE: f32 = 0.12;
+const LAP_SIZE: f32 = 0.05;
+const PERCENT_SIZE: f32 = 0.06;
+const LAPS_SHOWN: usize = 3;
+const CONFIRM_QUIT_SECONDS: f64 = 2.0;
+const LCD_UNLIT_ALPHA: u8 = 0x18;
+const DVD_TRAIL_FADE: u8 = 0x30;
+const MAX_DVD_LOGOS: usize = 8;
+// The colors the other logos of `--dvd-multi` change between as they bounce
+const DVD_COLORS: [Color; 6] = [
+    Color::RGB(255, 64, 64),
+    Color::RGB(255, 160, 0),
+    Color::RGB(255, 230, 0),
+    Color::RGB(64, 220, 64),
+    Color::RGB(64, 160, 255),
+    Color::RGB(200, 96, 255),
+];
+const PROGRESS_BAR_SIZE: f32 = 0.03;
+const NAG_FIRST_INTERVAL: f64 = 60.0;
+const NAG_MAX_COUNT: u32 = 6;
+const ZOOM_FINAL_SECONDS: f64 = 2.0;
+const ZOOM_FINAL_SCALE: f32 = 1.5;
+const PROGRESS_BAR_UNFILLED_ALPHA: u8 = 0x30;
+const BORDER_FLASH_HZ: f64 = 1.0;
+const METRONOME_SIZE: f32 = 0.04;
+const METRONOME_FLASH: f64 = 0.2;
+
+// The exit status when the timer is quit before it finishes. Errors exit with 1.
+const EXIT_CANCELLED: u8 = 2;
+const DEBUG_LAYOUT_COLOR: Color = Color::RGB(255, 0, 255);
+const CURSOR_IDLE_SECONDS: f64 = 2.0;
+const FLASH_SCREEN_COLOR: Color = Color::RGB(255, 0, 0);
+const FLASH_SCREEN_DURATION: f64 = 2.0;
+const FLASH_SCREEN_INTERVAL: f64 = 0.25;
+const FPS_OVERLAY_SIZE: f32 = 0.04;
+const FPS_SMOOTHING: f32 = 0.1;
+const QUEUE_OVERLAY_SIZE: f32 = 0.05;
+const BASE_DPI: f32 = 96.0;
+const HIDDEN_FRAME_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
 
 #[derive(PartialEq)]
 enum DisplayMode {
@@ -31,7 +108,7 @@ enum DisplayMode {
     DVD,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 struct Velocity {
     x: i32,
     y: i32,
@@ -46,32 +123,54 @@ struct TimerDisplay {
     velocity: Option<Velocity>,
 }
 
-#[derive(PartialEq)]
-enum TimerMode {
-    Default,
-    Stopwatch,
+#[derive(Clone, Copy, Debug, Default)]
+struct TimeFormat {
+    hide_hundredths: bool,
+    round_up: bool,
+    // With `--auto-precision`, the hundredths only show once the time is under this
+    hundredths_below: Option<f64>,
 }
 
-const USAGE: &str = r#"
-Usage: timer [options] [timer]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Numerals {
+    Ascii,
+    Arabic,
+    Devanagari,
+}
 
-Start a timer that counts down to the time specified. Press the <space> key to pause it.
+impl Numerals {
+    // Each supported digit system keeps 0-9 in a contiguous
+    // block, so only the codepoint for zero needs to be known.
+    fn zero(&self) -> char {
+        match self {
+            Numerals::Ascii => '0',
+            Numerals::Arabic => '\u{0660}',
+            Numerals::Devanagari => '\u{0966}',
+        }
+    }
+}
 
-Timer:
-    hh:mm:ss        The format of the countdown timer
-                    (e.g. "10" is ten seconds, "1:30" is a minute and thirty seconds, etc.)
+/// Parse a timer given by the user. A timer with too many parts is likely a mix up
+/// on the command line, so the usage is printed along with the error.
+fn parse_timer(value: &str) -> Result<f64, String> {
+    parse_duration(value).inspect_err(|_| {
+        if !value.ends_with(['h', 'm', 's']) && value.split(':').count() > 3 {
+            println!("{}", options::USAGE);
+        }
+    })
+}
 
-Options:
-    --stopwatch     Start a timer that increments rather than counts down to a specified time
-                    This option does not require the "timer" argument.
-    --dvd           Have the timer bounce around the screen like a DVD screen saver
-"#;
+/// Parse a duration, either colon separated ("1:30") or with units ("1m30s")
+fn parse_duration(value: &str) -> Result<f64, String> {
+    // Durations like "5m" or "1h30m" spell out their units
+    // rather than using the colon separated format.
+    if value.ends_with(['h', 'm', 's']) {
+        return parse_timer_units(value);
+    }
 
-fn parse_timer(value: &String) -> Result<f64, String> {
     let timer_string_split = value.split(':');
 
     if timer_string_split.clone().count() > 3 {
-        println!("{}", USAGE);
         return Err(
             "Invalid timer: countdown timer can only have 3 parts at most (hh:mm:ss)".to_string(),
         );
@@ -83,315 +182,3191 @@ fn parse_timer(value: &String) -> Result<f64, String> {
     Ok(timer_string_split
         .rev()
         .enumerate()
-        .fold(0, |acc, (i, time_string)| {
-            let parsed_time_string = time_string.parse::<u32>().ok().unwrap();
-            acc + parsed_time_string * u32::pow(60, i as u32)
-        }) as f64)
+        .try_fold(0, |acc, (i, time_string)| {
+            let parsed_time_string = time_string
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid timer: \"{}\"", value))?;
+            Ok::<u32, String>(acc + parsed_time_string * u32::pow(60, i as u32))
+        })? as f64)
 }
 
-fn main() -> Result<(), String> {
-    let mut args = ::std::env::args();
-    let mut timer: Option<f64> = None;
-    let mut display_mode = DisplayMode::Default;
-    let mut timer_mode = TimerMode::Default;
+fn parse_timer_units(value: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid timer: \"{}\"", value);
+    let mut seconds = 0.0;
+    let mut number = String::new();
 
-    // Shift one to move off the executable name
-    args.next();
+    for c in value.chars() {
+        let unit = match c {
+            'h' => 60.0 * 60.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => {
+                number.push(c);
+                continue;
+            }
+        };
 
-    for arg in args {
-        match arg.as_str() {
-            "--stopwatch" => timer_mode = TimerMode::Stopwatch,
-            "--dvd" => display_mode = DisplayMode::DVD,
-            _ => timer = Some(parse_timer(&arg)?),
+        let amount = number.parse::<f64>().map_err(|_| invalid())?;
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(invalid());
         }
+
+        seconds += amount * unit;
+        number.clear();
     }
 
-    // Redeclare the timer so we can just reference the value directly.
-    let mut timer = match timer_mode {
-        TimerMode::Default => {
-            if timer == None {
-                println!("{}", USAGE);
-                return Err("Missing timer".to_string());
-            }
+    Ok(seconds)
+}
 
-            timer.unwrap()
-        },
-        TimerMode::Stopwatch => {
-            0.0f64
-        },
+fn format_timer(time: f64, time_format: TimeFormat) -> String {
+    let time = f64::max(time, 0.0);
+
+    let hide_hundredths = time_format.hide_hundredths
+        || time_format
+            .hundredths_below
+            .is_some_and(|hundredths_below| time >= hundredths_below);
+
+    if hide_hundredths {
+        // Flooring matches how the time would read with the hundredths
+        // shown, but rounding up keeps "1" on screen right until zero.
+        let seconds = match time_format.round_up {
+            true => time.ceil(),
+            _ => time.floor(),
+        } as u64;
+
+        return format!(
+            "{:0>2}:{:0>2}:{:0>2}",
+            seconds / 60 / 60,
+            seconds / 60 % 60,
+            seconds % 60,
+        );
+    }
+
+    // Round to the hundredth once up front and split the whole number of
+    // hundredths into each part. Rounding each part on its own would let
+    // something like 59.999 show up as "00:00:60.00" instead of carrying
+    // over into the minutes.
+    let hundredths = (time * 100.0).round() as u64;
+
+    format!(
+        "{:0>2}:{:0>2}:{:0>2}.{:0>2}",
+        hundredths / 100 / 60 / 60,
+        hundredths / 100 / 60 % 60,
+        hundredths / 100 % 60,
+        hundredths % 100,
+    )
+}
+
+/// Write a duration out with units, the way `parse_timer` reads them (e.g. "1h30m").
+/// Parts that are zero are left out.
+fn format_timer_units(time: f64) -> String {
+    // Split up a whole number of hundredths, like `format_timer` does, so
+    // that 90.1 comes out as "1m30.1s" rather than "1m30.099999999999994s"
+    let hundredths = (f64::max(time, 0.0) * 100.0).round() as u64;
+    let hours = hundredths / 100 / 60 / 60;
+    let minutes = hundredths / 100 / 60 % 60;
+    let seconds = hundredths % 6000;
+
+    let mut formatted = String::new();
+    if hours > 0 {
+        formatted.push_str(&format!("{}h", hours));
+    }
+    if minutes > 0 {
+        formatted.push_str(&format!("{}m", minutes));
+    }
+    if seconds > 0 || formatted.is_empty() {
+        formatted.push_str(&match seconds % 100 {
+            0 => format!("{}s", seconds / 100),
+            fraction => format!(
+                "{}.{}s",
+                seconds / 100,
+                format!("{:0>2}", fraction).trim_end_matches('0')
+            ),
+        });
+    }
+
+    formatted
+}
+
+/// How long from `time_of_day` (in seconds since midnight) until the clock next reaches
+/// a multiple of `interval`. Right on a boundary, that's the whole interval until the next one.
+fn until_next_boundary(time_of_day: f64, interval: f64) -> f64 {
+    interval - time_of_day % interval
+}
+
+/// Round a timer to the nearest whole second. A timer of less than half a
+/// second still gets one, rather than finishing before it's started.
+fn round_to_second(timer: f64) -> f64 {
+    match timer > 0.0 {
+        true => timer.round().max(1.0),
+        _ => timer,
+    }
+}
+
+/// How many beats at `bpm` beats per minute have started after `elapsed` seconds,
+/// and how far into the latest of them it is, from 0 to 1
+fn beat_position(elapsed: f64, bpm: f64) -> (u64, f64) {
+    let beats = f64::max(elapsed, 0.0) * bpm / 60.0;
+
+    (beats.floor() as u64, beats.fract())
+}
+
+/// The velocity the `index`th bouncing logo starts out with. Each one heads off in a
+/// different direction, and some move faster across or down, so that they spread out.
+fn seed_velocity(index: usize) -> Velocity {
+    let x = VELOCITY_SPEED + (index % 2) as i32;
+    let y = VELOCITY_SPEED + (index / 2 % 2) as i32;
+
+    match index % 4 {
+        0 => Velocity { x, y },
+        1 => Velocity { x: -x, y },
+        2 => Velocity { x, y: -y },
+        _ => Velocity { x: -x, y: -y },
+    }
+}
+
+/// The other `count - 1` logos of `--dvd-multi`, each `width` by `height`, spread out
+/// across the window from the top left, along with the color each one starts with
+fn seed_logos(
+    count: usize,
+    window_width: i32,
+    window_height: i32,
+    width: u32,
+    height: u32,
+) -> Vec<(TimerDisplay, usize)> {
+    let room_across = (window_width - width as i32).max(0) as usize;
+    let room_down = (window_height - height as i32).max(0) as usize;
+
+    (1..count)
+        .map(|i| {
+            let logo = TimerDisplay {
+                x: (room_across * i / count) as i32,
+                y: (room_down * i / count) as i32,
+                width,
+                height,
+                velocity: Some(seed_velocity(i)),
+            };
+
+            (logo, (i - 1) % DVD_COLORS.len())
+        })
+        .collect()
+}
+
+/// Move a bouncing display along by its velocity, turning it back from any edge of the
+/// window it reaches. `top` and `bottom` are where the text really starts and ends, as
+/// fractions of the display's height, so that it's the text that touches the edges rather
+/// than the font's padding. Returns whether it bounced.
+fn bounce(
+    timer_display: &mut TimerDisplay,
+    window_width: i32,
+    window_height: i32,
+    top: f32,
+    bottom: f32,
+) -> bool {
+    let before = match timer_display.velocity {
+        Some(velocity) => velocity,
+        None => return false,
     };
+    let mut velocity = before;
 
-    let mut timer_display = TimerDisplay {
-        x: 0,
-        y: 0,
-        width: 0,
-        height: 0,
-        velocity: None,
+    timer_display.x += velocity.x;
+    timer_display.y += velocity.y;
+
+    if timer_display.x <= 0 {
+        velocity.x = velocity.x.abs();
+    }
+
+    if (timer_display.x + timer_display.width as i32) >= window_width {
+        velocity.x = -velocity.x.abs();
+    }
+
+    if (timer_display.y + (timer_display.height as f32 * top) as i32) <= 0 {
+        velocity.y = velocity.y.abs();
+    }
+
+    if (timer_display.y + (timer_display.height as f32 * bottom) as i32) >= window_height {
+        velocity.y = -velocity.y.abs();
+    }
+
+    timer_display.velocity = Some(velocity);
+    velocity != before
+}
+
+/// Count how many whole units of `unit_seconds` are in `time`. A countdown
+/// rounds up so the last unit stays on screen until the timer finishes.
+fn count_units(time: f64, unit_seconds: f64, round_up: bool) -> u64 {
+    let units = f64::max(time, 0.0) / unit_seconds;
+    let units = match round_up {
+        true => units.ceil(),
+        _ => units.floor(),
     };
 
-    // Add a velocity to the timer_display since it'll be bouncin' around the place
-    if display_mode == DisplayMode::DVD {
-        timer_display.velocity = Some(Velocity {
-            x: VELOCITY_SPEED,
-            y: VELOCITY_SPEED,
-        });
+    units as u64
+}
+
+/// Find the label for a round of a repeated timer. When there are
+/// fewer labels than rounds, the labels wrap back to the start.
+fn label_for_round(labels: &[String], round: usize) -> Option<&str> {
+    match labels.is_empty() {
+        true => None,
+        _ => Some(&labels[round % labels.len()]),
     }
+}
 
-    let mut window_width: i32 = WIDTH as i32;
-    let mut window_height: i32 = HEIGHT as i32;
+fn parse_numerals(value: &str) -> Result<Numerals, String> {
+    match value {
+        "ascii" => Ok(Numerals::Ascii),
+        "arabic" => Ok(Numerals::Arabic),
+        "devanagari" => Ok(Numerals::Devanagari),
+        _ => Err(format!(
+            "Invalid numerals: unknown numeral system \"{}\"",
+            value
+        )),
+    }
+}
 
-    let sdl_context = sdl2::init()?;
-    let video_subsystem = sdl_context.video()?;
-    let window = video_subsystem
-        .window("timer", window_width as u32, window_height as u32)
-        .position_centered()
-        .resizable()
-        .build()
-        .map_err(|e| e.to_string())?;
+fn translate_digits(value: &str, numerals: Numerals) -> String {
+    value
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(digit) => char::from_u32(numerals.zero() as u32 + digit).unwrap(),
+            None => c,
+        })
+        .collect()
+}
 
-    let font_bytes = include_bytes!("../assets/Roboto-Regular.ttf");
-    let ttf_handler = ttf::init().unwrap();
-    let font = ttf_handler.load_font_from_rwops(RWops::from_bytes(font_bytes).unwrap(), 512)?;
+/// How much to scale the window by for a display with the given DPI. Low
+/// or nonsense values are ignored so the window is never made smaller.
+fn dpi_scale(dpi: f32) -> f32 {
+    match dpi.is_finite() {
+        true => f32::max(dpi / BASE_DPI, 1.0),
+        _ => 1.0,
+    }
+}
 
-    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
-    let texture_creator = canvas.texture_creator();
-    let background_color = Color::RGB(0, 0, 0);
-    canvas.set_draw_color(background_color);
-    canvas.clear();
-    canvas.present();
+/// How much of the width the text takes up when there's room kept for at least
+/// `min_chars` glyphs of `glyph_width`. Text longer than that takes the full width.
+fn reserved_fraction(text_width: u32, glyph_width: u32, min_chars: u32) -> f32 {
+    let reserved = u32::max(text_width, glyph_width.saturating_mul(min_chars));
+    text_width as f32 / reserved.max(1) as f32
+}
 
-    let mut event_pump = sdl_context.event_pump().unwrap();
-    let mut current_time = Instant::now();
-    let mut blink_timer = 0.0;
-    let mut paused = false;
-    let mut user_notified_finished_timer = false;
+/// The texture for a single character in the given color. Each one is only
+/// rendered the first time it's needed, and kept for the frames after that.
+fn glyph_texture<'a, 't>(
+    glyphs: &'a mut HashMap<(char, Color), Texture<'t>>,
+    font: &ttf::Font,
+    texture_creator: &'t TextureCreator<WindowContext>,
+    c: char,
+    color: Color,
+) -> Result<&'a mut Texture<'t>, String> {
+    match glyphs.entry((c, color)) {
+        Entry::Occupied(entry) => Ok(entry.into_mut()),
+        Entry::Vacant(entry) => {
+            let mut texture = font
+                .render(&c.to_string())
+                .solid(color)
+                .map_err(|e| e.to_string())?
+                .as_texture(texture_creator)
+                .map_err(|e| e.to_string())?;
+            texture.set_blend_mode(BlendMode::Blend);
 
-    'main_loop: loop {
-        // The timer is always considered active in Stopwatch mode.
-        let active_timer = timer > 0.0 || TimerMode::Stopwatch == timer_mode;
-        if !active_timer && !user_notified_finished_timer {
-            canvas
-                .window_mut()
-                .flash(sdl2::video::FlashOperation::UntilFocused)?;
-            user_notified_finished_timer = true;
+            Ok(entry.insert(texture))
+        }
+    }
+}
 
-            // For XDG desktops (besides macOS), we can use D-Bus to send a
-            // Desktop notification and let the user know that the timer
-            // has finished. This code should be moved into a module.
-            #[cfg(all(unix, not(target_os = "macos")))]
-            {
-                let connection = Connection::get_private(dbus::ffidisp::BusType::Session)
-                    .map_err(|e| e.to_string())?;
+/// Where `rect` ends up once `area` is flipped across, down, or both
+fn mirror_rect(rect: Rect, area: Rect, horizontal: bool, vertical: bool) -> Rect {
+    let x = match horizontal {
+        true => area.x() * 2 + area.width() as i32 - rect.x() - rect.width() as i32,
+        _ => rect.x(),
+    };
+    let y = match vertical {
+        true => area.y() * 2 + area.height() as i32 - rect.y() - rect.height() as i32,
+        _ => rect.y(),
+    };
 
-                let mut message = Message::new_method_call(
-                    "org.freedesktop.Notifications",
-                    "/org/freedesktop/Notifications",
-                    "org.freedesktop.Notifications",
-                    "Notify",
-                )?;
+    Rect::new(x, y, rect.width(), rect.height())
+}
 
-                let program_name = "timer";
-                let id: u32 = 0;
-                let icon = "";
-                let summary = "Timer";
-                let body = "Time's up!";
-                let actions =
-                    MessageItem::Array(MessageItemArray::new(vec![], "as".into()).unwrap());
-                let hints =
-                    MessageItem::Array(MessageItemArray::new(vec![], "a{sv}".into()).unwrap());
-                let timeout = 5000;
-
-                message.append_items(&[
-                    program_name.clone().into(),
-                    id.into(),
-                    icon.into(),
-                    summary.into(),
-                    body.into(),
-                    actions,
-                    hints,
-                    timeout.into(),
-                ]);
-
-                connection
-                    .send(message)
-                    .map_err(|_| String::from("Could not send Desktop Notification Message"))?;
-            }
-
-            #[cfg(target_os = "macos")]
-            {
-                let bundle = mac_notification_sys::get_bundle_identifier_or_default("iterm");
-                mac_notification_sys::set_application(&bundle).unwrap();
-                let _ = mac_notification_sys::Notification::new()
-                    .title("Timer")
-                    .message("Time's up!")
-                    .sound("Ping")
-                    .send()
-                    .unwrap();
-            }
+/// Where each copy of an image goes to tile it across the window from the
+/// top left corner. The copies along the right and bottom edges are cut off.
+fn tile_rects(
+    image_width: u32,
+    image_height: u32,
+    window_width: i32,
+    window_height: i32,
+) -> Vec<Rect> {
+    let (image_width, image_height) = (image_width.max(1), image_height.max(1));
+
+    (0..window_height.max(0))
+        .step_by(image_height as usize)
+        .flat_map(|y| {
+            (0..window_width.max(0))
+                .step_by(image_width as usize)
+                .map(move |x| Rect::new(x, y, image_width, image_height))
+        })
+        .collect()
+}
+
+/// Lay columns of text with the given aspect ratios (width over height) across `area`.
+/// Each column gets an even share of the width and is centered down the height,
+/// shrinking to keep its proportions when it would be too tall to fit.
+fn side_by_side(aspects: &[f32], area: Rect) -> Vec<Rect> {
+    let column_width = area.width() as f32 / aspects.len().max(1) as f32;
+
+    aspects
+        .iter()
+        .enumerate()
+        .map(|(i, aspect)| {
+            let height = match aspect {
+                0.0 => area.height() as f32,
+                _ => f32::min(column_width / aspect, area.height() as f32),
+            };
+            let width = f32::min(height * aspect, column_width);
+
+            Rect::new(
+                area.x() + (column_width * i as f32 + (column_width - width) / 2.0) as i32,
+                area.y() + ((area.height() as f32 - height) / 2.0) as i32,
+                width as u32,
+                height as u32,
+            )
+        })
+        .collect()
+}
+
+/// Stack rows of text with the given aspect ratios (width over height) down `area`.
+/// Each row gets an even share of the height and is centered across the width,
+/// shrinking to keep its proportions when it would be too wide to fit.
+fn stack_rows(aspects: &[f32], area: Rect) -> Vec<Rect> {
+    let row_height = area.height() as f32 / aspects.len().max(1) as f32;
+
+    aspects
+        .iter()
+        .enumerate()
+        .map(|(i, aspect)| {
+            let width = f32::min(row_height * aspect, area.width() as f32);
+            let height = match aspect {
+                0.0 => row_height,
+                _ => width / aspect,
+            };
+
+            Rect::new(
+                area.x() + ((area.width() as f32 - width) / 2.0) as i32,
+                area.y() + (row_height * i as f32 + (row_height - height) / 2.0) as i32,
+                width as u32,
+                height as u32,
+            )
+        })
+        .collect()
+}
+
+/// Render each part of the text in its own color, and fit the parts into `area`
+/// one under the other when `vertical`, or side by side otherwise. SDL_ttf can't
+/// render empty text, so an empty part is left as a blank space in its place.
+fn render_parts<'a>(
+    font: &ttf::Font,
+    texture_creator: &'a TextureCreator<WindowContext>,
+    parts: &[(String, Color)],
+    vertical: bool,
+    area: Rect,
+) -> Result<Vec<(Texture<'a>, Rect)>, String> {
+    let textures = parts
+        .iter()
+        .map(|(text, color)| match text.is_empty() {
+            true => Ok(None),
+            _ => font
+                .render(text)
+                .solid(*color)
+                .map_err(|e| e.to_string())?
+                .as_texture(texture_creator)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let aspects: Vec<f32> = textures
+        .iter()
+        .map(|texture| {
+            texture.as_ref().map_or(0.0, |texture| {
+                let query = texture.query();
+                query.width as f32 / query.height.max(1) as f32
+            })
+        })
+        .collect();
+    let rects = match vertical {
+        true => stack_rows(&aspects, area),
+        _ => side_by_side(&aspects, area),
+    };
+
+    Ok(textures
+        .into_iter()
+        .zip(rects)
+        .filter_map(|(texture, rect)| Some((texture?, rect)))
+        .collect())
+}
+
+/// Fill in a notification template for the timer with the given label, with a
+/// duration of `seconds` written the same way in every notification
+fn notification_body(template: &str, label: &str, seconds: f64) -> String {
+    let duration = format_timer(
+        seconds,
+        TimeFormat {
+            hide_hundredths: true,
+            round_up: false,
+            hundredths_below: None,
+        },
+    );
+
+    notification::fill_template(template, label, &duration)
+}
+
+/// How much of a countdown from `initial` is done with `timer` seconds left, from 0 to 1.
+/// A countdown from zero has nothing left to do, so it's already done.
+fn progress_fraction(timer: f64, initial: f64) -> f64 {
+    match initial > 0.0 {
+        true => (1.0 - timer / initial).clamp(0.0, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// A fraction from 0 to 1 as a whole percentage, like "42%"
+fn format_percent(fraction: f64) -> String {
+    format!("{}%", (fraction * 100.0).round())
+}
+
+/// Where the progress bar goes in a window of the given size
+fn progress_bar_rect(window_width: i32, window_height: i32) -> Rect {
+    let height = ((window_height as f32 * PROGRESS_BAR_SIZE) as u32).max(1);
+
+    Rect::new(
+        0,
+        window_height - height as i32,
+        window_width.max(1) as u32,
+        height,
+    )
+}
+
+/// Where the first of `shown` laps goes. They sit just under the time when there's
+/// room, and otherwise move up to stay above whatever is along the bottom.
+fn laps_top(time_bottom: i32, bottom: i32, shown: usize, lap_height: u32) -> i32 {
+    i32::min(time_bottom, bottom - (lap_height * shown as u32) as i32).max(0)
+}
+
+/// The time remaining that a click at `x` on the progress bar seeks to.
+/// The bar fills from the left, so the further right, the less time is left.
+fn seek_time(x: i32, bar: Rect, initial: f64) -> f64 {
+    let fraction = ((x - bar.x()) as f64 / bar.width() as f64).clamp(0.0, 1.0);
+
+    initial * (1.0 - fraction)
+}
+
+/// How long to wait before the reminder after `nags_sent` earlier ones.
+/// The wait doubles each time (e.g. 1 minute, then 2, then 4).
+fn nag_interval(nags_sent: u32) -> f64 {
+    NAG_FIRST_INTERVAL * 2f64.powi(nags_sent as i32)
+}
+
+/// Whether the next reminder is due `since_last` seconds after the last one (or the finish).
+/// A fixed `repeat_every` keeps going for as long as it takes, whereas `--nag` backs off
+/// and gives up after a few.
+fn reminder_due(nags_sent: u32, since_last: f64, repeat_every: Option<f64>) -> bool {
+    match repeat_every {
+        Some(interval) => since_last >= interval,
+        None => nags_sent < NAG_MAX_COUNT && since_last >= nag_interval(nags_sent),
+    }
+}
+
+// Set by `--quiet` to keep the warnings off of stderr
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Print a warning about something that didn't work but isn't worth quitting over
+pub fn warn(message: impl std::fmt::Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        eprintln!("Warning: {}", message);
+    }
+}
+
+/// Whether the colors are swapped with `--invert-final`, which lasts
+/// for the final stretch of the countdown but not once it reaches zero.
+fn invert_colors(invert_final: Option<f64>, remaining: f64) -> bool {
+    invert_final.is_some_and(|seconds| remaining > 0.0 && remaining <= seconds)
+}
+
+/// How much bigger the text is drawn with `--zoom-final`, growing
+/// steadily from its usual size to the full zoom as the time runs out.
+fn zoom_final_scale(remaining: f64) -> f32 {
+    match remaining > 0.0 && remaining < ZOOM_FINAL_SECONDS {
+        true => 1.0 + (ZOOM_FINAL_SCALE - 1.0) * (1.0 - remaining / ZOOM_FINAL_SECONDS) as f32,
+        _ => 1.0,
+    }
+}
+
+/// Whether the timer has been open for longer than `--max-runtime` allows
+fn runtime_exceeded(max_runtime: Option<f64>, runtime: f64) -> bool {
+    max_runtime.is_some_and(|max_runtime| runtime >= max_runtime)
+}
+
+/// The four sides of a border `thickness` pixels thick around the inside of the window
+fn border_rects(window_width: i32, window_height: i32, thickness: u32) -> [Rect; 4] {
+    let thickness = thickness.clamp(1, (window_width.min(window_height) / 2).max(1) as u32);
+    let t = thickness as i32;
+    let inner_height = (window_height - 2 * t).max(0) as u32;
+
+    [
+        Rect::new(0, 0, window_width as u32, thickness),
+        Rect::new(0, window_height - t, window_width as u32, thickness),
+        Rect::new(0, t, thickness, inner_height),
+        Rect::new(window_width - t, t, thickness, inner_height),
+    ]
+}
+
+/// Where a window of the given size goes to be centered on a display. A window
+/// bigger than the display is kept to its top left corner rather than hanging
+/// off onto the displays next to it.
+fn center_on(bounds: Rect, width: i32, height: i32) -> (i32, i32) {
+    (
+        bounds.x() + ((bounds.width() as i32 - width) / 2).max(0),
+        bounds.y() + ((bounds.height() as i32 - height) / 2).max(0),
+    )
+}
+
+/// Where to draw an image so that it covers the whole window, keeping its proportions.
+/// Whatever sticks out past the window on one side or the other is cut off evenly.
+fn cover_rect(image_width: u32, image_height: u32, window_width: i32, window_height: i32) -> Rect {
+    let scale = f32::max(
+        window_width as f32 / image_width.max(1) as f32,
+        window_height as f32 / image_height.max(1) as f32,
+    );
+    let width = (image_width as f32 * scale) as u32;
+    let height = (image_height as f32 * scale) as u32;
+
+    Rect::new(
+        (window_width - width as i32) / 2,
+        (window_height - height as i32) / 2,
+        width,
+        height,
+    )
+}
+
+/// Whether a second attempt to quit at `now` follows close
+/// enough after the first one to confirm it.
+fn quit_confirmed(pending_quit: &Option<(Instant, String)>, now: Instant) -> bool {
+    pending_quit.as_ref().is_some_and(|(asked_at, _)| {
+        now.duration_since(*asked_at).as_secs_f64() < CONFIRM_QUIT_SECONDS
+    })
+}
+
+/// Describe a frame time in seconds as a frame rate and a time per frame
+fn describe_frame_time(seconds: f32) -> String {
+    format!("{:.0} fps {:.1} ms", 1.0 / seconds, seconds * 1000.0)
+}
+
+fn main() -> Result<ExitCode, String> {
+    let Options {
+        mut timer,
+        display_mode,
+        timer_mode,
+        restart_on_focus,
+        start_paused,
+        mut text_color,
+        mut background_color,
+        dvd_elapsed,
+        flash_screen,
+        message,
+        static_finish,
+        min_font_size,
+        alert_flash,
+        alert_color,
+        video_driver,
+        headless,
+        verbose,
+        debug_layout,
+        hide_cursor,
+        countdown_beeps,
+        read_stdin,
+        shadow,
+        shadow_color,
+        shadow_offset,
+        time_format,
+        auto_quit_after,
+        repeat,
+        labels,
+        notify_template,
+        speak_at,
+        speak_command,
+        smooth_resize,
+        inhibit_sleep,
+        key_bindings,
+        mut numerals,
+        remember_geometry,
+        mirror,
+        mirror_vertical,
+        show_fps,
+        unit_seconds,
+        unit_label,
+        preroll,
+        preroll_beeps,
+        list_fonts,
+        flip_clock,
+        minimized,
+        scale_with_monitor_dpi,
+        laps_out,
+        confirm_quit,
+        lcd,
+        pause_on_blur,
+        write_to,
+        warn_at,
+        warn_flash,
+        padding,
+        text_size,
+        dvd_trail,
+        announce_start,
+        start_template,
+        progress_bar,
+        display,
+        fullscreen,
+        alarm_fade_in,
+        mut theme,
+        grace,
+        tile,
+        borderless,
+        always_on_top,
+        audio_device,
+        list_audio_devices,
+        nag,
+        vertical,
+        test_notification,
+        cues,
+        invert_final,
+        quiet,
+        zoom_final,
+        show_both,
+        min_width_chars,
+        rpc,
+        dump_metrics,
+        lock,
+        resume,
+        hollow,
+        outline_color,
+        max_runtime,
+        convert,
+        border_flash,
+        border_color,
+        border_width,
+        stopwatches: stopwatch_count,
+        chess,
+        clipboard,
+        opacity,
+        next,
+        history,
+        background_image,
+        background_tile,
+        mirror_file,
+        round_start,
+        metronome,
+        metronome_click,
+        repeat_notification,
+        once,
+        show_percent,
+        speak_countdown,
+        dvd_logos,
+    } = options::parse_args(std::env::args().skip(1))?;
+
+    QUIET.store(quiet, Ordering::Relaxed);
+
+    // Try out the finish notification and the alarm without running a timer, to make
+    // sure they work on this machine before counting on them for a long timer.
+    if test_notification {
+        let label = labels.first().map_or("Timer", |label| label.as_str());
+        let notified = notification::send(
+            label,
+            &notification_body(&notify_template, label, timer.unwrap_or(0.0)),
+        );
+
+        match &notified {
+            Ok(()) => println!("Sent a test notification"),
+            Err(e) => println!("Could not send a test notification: {}", e),
         }
 
-        /****************************
-         *** POLL EVENTS ************
-         ****************************/
+        match sdl2::init()
+            .and_then(|sdl_context| sdl_context.audio())
+            .and_then(|audio_subsystem| {
+                audio::Beeper::open(&audio_subsystem, audio_device.as_deref())
+            }) {
+            Ok(mut beeper) => {
+                beeper.beep(audio::Beep::Final);
+                println!("Played the alarm");
 
-        for event in event_pump.poll_iter() {
-            match event {
-                Event::Quit { .. }
-                | Event::KeyDown {
-                    keycode: Some(Keycode::Escape),
-                    ..
-                } => break 'main_loop,
-                Event::KeyDown {
-                    keycode: Some(Keycode::Space),
-                    ..
-                } => {
-                    if active_timer {
-                        paused = !paused;
-                    }
-                }
-                Event::Window { win_event, .. } => {
-                    if let WindowEvent::Resized(w, h) = win_event {
-                        window_width = w;
-                        window_height = h;
-                    }
-                }
-                _ => {}
+                // The beep plays in the background, so wait for it to finish
+                std::thread::sleep(std::time::Duration::from_secs(1));
             }
+            Err(e) => println!("Could not play the alarm: {}", e),
         }
 
-        /****************************
-         *** RENDER ******************
-         ****************************/
-
-        let time_to_format = f64::max(timer, 0.0);
+        return match notified {
+            Ok(()) => Ok(ExitCode::SUCCESS),
+            _ => Ok(ExitCode::FAILURE),
+        };
+    }
 
-        // If we don't call `floor()` on the hour and minutes, the format
-        // string will round the whole number portion of the float and
-        // give us oddly formatted times that don't make any sense.
-        let formatted_timer = format!(
-            "{:0>2.0}:{:0>2.0}:{:0>5.2}",
-            (time_to_format / 60.0 / 60.0).floor(),
-            (time_to_format / 60.0 % 60.0).floor(),
-            time_to_format % 60.0,
+    if let Some(convert) = convert {
+        println!("{}s", convert);
+        println!("{}", format_timer_units(convert));
+        println!(
+            "{}",
+            format_timer(
+                convert,
+                TimeFormat {
+                    hide_hundredths: convert.fract() == 0.0,
+                    round_up: false,
+                    hundredths_below: None,
+                },
+            )
         );
 
-        let font_color = match paused {
-            true => Color::RGB(120, 120, 120),
-            _ => Color::RGB(255, 255, 255),
-        };
+        return Ok(ExitCode::SUCCESS);
+    }
 
-        let pre_texture = font.render(&formatted_timer).solid(font_color).unwrap();
-        let texture = pre_texture.as_texture(&texture_creator).unwrap();
-        canvas.set_draw_color(background_color);
-        canvas.clear();
+    if list_audio_devices {
+        let audio_subsystem = sdl2::init()?.audio()?;
+        for device in audio::playback_devices(&audio_subsystem) {
+            println!("{}", device);
+        }
 
-        match display_mode {
-            DisplayMode::DVD => {
-                timer_display.x = timer_display.x + timer_display.velocity.unwrap().x;
-                timer_display.y = timer_display.y + timer_display.velocity.unwrap().y;
-                timer_display.width = (window_width as f32 * DVD_FONT_SCALE) as u32;
-                timer_display.height = (window_height as f32 * DVD_FONT_SCALE) as u32;
+        return Ok(ExitCode::SUCCESS);
+    }
 
-                if timer_display.x <= 0 {
-                    timer_display.velocity.as_mut().unwrap().x = VELOCITY_SPEED;
-                }
+    // The font metrics that the DVD bounce works from, to help track down a bad bounce
+    if dump_metrics {
+        let ttf_handler = ttf::init().map_err(|e| e.to_string())?;
+        let font =
+            ttf_handler.load_font_from_rwops(RWops::from_bytes(FONT_BYTES)?, FONT_POINT_SIZE)?;
+        let height = font.height().max(1) as f32;
 
-                if (timer_display.x + timer_display.width as i32) >= window_width {
-                    timer_display.velocity.as_mut().unwrap().x = -VELOCITY_SPEED;
-                }
+        println!("height: {}", font.height());
+        println!("ascent: {}", font.ascent());
+        println!("descent: {}", font.descent());
+        println!(
+            "padding above the ascent: {:.2}%",
+            (font.height() - font.ascent()) as f32 / height * 100.0
+        );
+        println!(
+            "height down to the baseline: {:.2}%",
+            (font.height() + font.descent()) as f32 / height * 100.0
+        );
 
-                // The font has some padding above it. To make the timer properly hit the top of
-                // the window by ignoring the padding, we need to calculate the space between
-                // the font ascent and the font's top. This will give us the padding value.
-                let font_padding_above_ascent_percentage =
-                    (font.height() - font.ascent()) as f32 / font.height() as f32;
-                let padding =
-                    ((timer_display.height as f32) * font_padding_above_ascent_percentage) as i32;
-                if (timer_display.y + padding) <= 0 {
-                    timer_display.velocity.as_mut().unwrap().y = VELOCITY_SPEED;
-                }
+        return Ok(ExitCode::SUCCESS);
+    }
 
-                // There is also some padding under the font's baseline which makes the bounce
-                // occur earlier than it should. Here we'll take the baseline and add it to
-                // `y` on the timer_display to find where the true bottom of the text is.
-                //
-                // NOTE: If we were to ever add characters that go below
-                // baseline, then the bounce effect would break since
-                // we're calcluating the bounce from the baseline.
-                let font_height_from_baseline_percentage =
-                    (font.height() + font.descent()) as f32 / font.height() as f32;
-                let true_height =
-                    ((timer_display.height as f32) * font_height_from_baseline_percentage) as i32;
-                if (timer_display.y + true_height) >= window_height {
-                    timer_display.velocity.as_mut().unwrap().y = -VELOCITY_SPEED;
-                }
-            }
-            DisplayMode::Default => {
-                // Calculate the time display based on the window width and
-                // height. We run this every frame just in case the user
-                // has resized the window which changes the font size.
-                timer_display.x = (window_width as f32 * TEXT_PADDING) as i32;
-                timer_display.y = (window_height as f32 * TEXT_PADDING) as i32;
-                timer_display.width = (window_width as f32 * TEXT_SIZE) as u32;
-                timer_display.height = (window_height as f32 * TEXT_SIZE) as u32;
+    if list_fonts {
+        for font in fonts::list_fonts() {
+            println!("{}", font.display());
+        }
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // Timers piped in on stdin run after the one given as an
+    // argument, or make up the whole queue if there wasn't one.
+    let mut queued_timers = VecDeque::new();
+    if read_stdin {
+        for line in std::io::stdin().lines() {
+            let line = line.map_err(|e| format!("Could not read stdin: {}", e))?;
+            if !line.trim().is_empty() {
+                queued_timers.push_back(parse_timer(line.trim())?);
             }
         }
 
-        // Once `active_timer` is false, we flash the completed
-        // timer on the screen every half second; so we need
-        // to set `flash_timer` every half second for it.
-        let flash_timer = (blink_timer % 1.0) < 0.5;
+        if queued_timers.is_empty() {
+            return Err("Missing timer: no timers were given on stdin".to_string());
+        }
 
-        if active_timer || flash_timer {
-            canvas
-                .copy(
-                    &texture,
-                    None,
-                    Rect::new(
-                        timer_display.x,
-                        timer_display.y,
-                        timer_display.width,
-                        timer_display.height,
-                    ),
-                )
-                .expect("Error writing texture");
+        if timer.is_none() {
+            timer = queued_timers.pop_front();
         }
+    }
 
-        canvas.present();
+    // The boundaries go by the local time, so the top of the hour matches the clock on the wall
+    if let Some(interval) = next {
+        let now = chrono::Local::now();
+        let time_of_day =
+            now.num_seconds_from_midnight() as f64 + now.nanosecond() as f64 / 1_000_000_000.0;
 
-        /****************************
-         *** UPDATE TIMER ************
-         ****************************/
+        timer = Some(until_next_boundary(time_of_day, interval));
+    }
 
-        let new_time = Instant::now();
-        let frame_time = new_time - current_time;
-        current_time = new_time;
-        let dt = frame_time.as_secs_f64();
+    if clipboard {
+        let text = sdl2::init()
+            .and_then(|sdl_context| sdl_context.video())
+            .and_then(|video_subsystem| video_subsystem.clipboard().clipboard_text())
+            .map_err(|e| format!("Could not read the clipboard: {}", e))?;
 
-        if active_timer && !paused {
-            match timer_mode {
-                TimerMode::Default => timer -= dt,
-                TimerMode::Stopwatch => timer += dt,
-            }
-        } else if !active_timer {
-            blink_timer += dt;
+        if text.trim().is_empty() {
+            return Err("Missing timer: the clipboard is empty".to_string());
         }
+
+        timer = Some(parse_timer(text.trim())?);
     }
 
-    Ok(())
-}
+    if round_start {
+        timer = timer.map(round_to_second);
 
-#[test]
-fn it_should_parse_a_time_with_only_seconds() {
-    assert_eq!(10.0, parse_timer(&"10".to_string()).unwrap());
-}
+        for queued_timer in queued_timers.iter_mut() {
+            *queued_timer = round_to_second(*queued_timer);
+        }
+    }
 
-#[test]
-fn it_should_parse_a_time_with_minutes_and_seconds() {
-    assert_eq!(70.0, parse_timer(&"01:10".to_string()).unwrap());
-}
+    // Held until the timer quits, when dropping it frees the name for the next one
+    let _instance_lock = once
+        .as_deref()
+        .map(once::InstanceLock::acquire)
+        .transpose()?;
 
-#[test]
-fn it_should_parse_a_time_with_hours_minutes_and_seconds() {
-    assert_eq!(3670.0, parse_timer(&"01:01:10".to_string()).unwrap());
+    let clock = SystemClock;
+
+    // A countdown left unfinished last time is only picked up when no timer was given
+    let saved = match resume && timer_mode == TimerMode::Default && timer.is_none() {
+        true => resume::load(),
+        _ => None,
+    };
+    if let Some(saved) = saved {
+        timer = Some(saved.initial);
+    }
+
+    // Redeclare the timer so we can just reference the value directly.
+    let timer = match timer_mode {
+        TimerMode::Default => {
+            if timer == None {
+                println!("{}", options::USAGE);
+                return Err("Missing timer".to_string());
+            }
+
+            timer.unwrap()
+        }
+        TimerMode::Stopwatch => 0.0f64,
+    };
+
+    let mut state = TimerState::new(timer_mode, timer, &clock).with_preroll(preroll as f64);
+
+    // Seeking doesn't count the time since the last run, so it picks up right where it was
+    if let Some(saved) = saved {
+        state = TimerState::new(timer_mode, saved.initial, &clock);
+        state.seek(saved.remaining, &clock);
+        state.set_paused(saved.paused, &clock);
+    } else if start_paused {
+        state.set_paused(true, &clock);
+    }
+
+    // Repeating the timer queues up copies of it ahead of anything else
+    for _ in 1..repeat {
+        queued_timers.push_front(timer);
+    }
+
+    // Which of the queued timers is running, starting from zero
+    let mut round = 0;
+
+    let mut time_file = write_to.map(time_file::TimeFile::new);
+    let mut mirror_file = mirror_file.map(time_file::MirrorFile::new);
+
+    if headless {
+        let interrupted = headless::catch_interrupt();
+        let mut started_at = chrono::Local::now().to_rfc3339();
+
+        // Each of the queued timers runs in turn, and is notified and
+        // recorded in the history as it finishes, the same as in the window
+        loop {
+            headless::run(
+                &mut state,
+                &clock,
+                time_format,
+                time_file.as_mut(),
+                &interrupted,
+            );
+
+            // Stopped with Ctrl-C
+            if state.is_active() {
+                break;
+            }
+
+            let label = label_for_round(&labels, round);
+            let body = notification_body(&notify_template, label.unwrap_or("Timer"), state.initial);
+            if let Err(e) = notification::send(label.unwrap_or("Timer"), &body) {
+                warn(format!("could not send the notification: {}", e));
+            }
+
+            if let Some(history) = &history {
+                history::record(
+                    history,
+                    &started_at,
+                    state.initial,
+                    label.unwrap_or(""),
+                    state.mode,
+                );
+            }
+
+            match queued_timers.pop_front() {
+                Some(next_timer) => {
+                    state.restart(next_timer, &clock);
+                    round += 1;
+                    started_at = chrono::Local::now().to_rfc3339();
+                }
+                None => break,
+            }
+        }
+
+        // A stopwatch has no finish, so it goes in the history once it's stopped
+        if let (Some(history), TimerMode::Stopwatch) = (&history, state.mode) {
+            history::record(
+                history,
+                &started_at,
+                state.display_time(&clock),
+                label_for_round(&labels, round).unwrap_or(""),
+                state.mode,
+            );
+        }
+
+        if resume && state.mode == TimerMode::Default {
+            resume::save_or_clear(&state, false, &clock);
+        }
+
+        return match state.is_active() && state.mode == TimerMode::Default {
+            true => Ok(ExitCode::from(EXIT_CANCELLED)),
+            _ => Ok(ExitCode::SUCCESS),
+        };
+    }
+
+    let mut timer_display = TimerDisplay {
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+        velocity: None,
+    };
+
+    // Add a velocity to the timer_display since it'll be bouncin' around the place
+    if display_mode == DisplayMode::DVD {
+        timer_display.velocity = Some(seed_velocity(0));
+    }
+
+    // The other logos of `--dvd-multi`, along with which of the colors each one is
+    // showing. They all show the same time, and are placed once the window's size is known.
+    let mut other_logos: Vec<(TimerDisplay, usize)> = Vec::new();
+
+    // The size the text is currently drawn at while easing towards the size
+    // the window calls for. This is only used with `--smooth-resize`.
+    let mut smoothed_size: Option<(f32, f32)> = None;
+
+    let mut window_width: i32 = WIDTH as i32;
+    let mut window_height: i32 = HEIGHT as i32;
+
+    // The driver has to be picked before SDL is initialized. When no driver is
+    // given, SDL falls back to the SDL_VIDEODRIVER variable and its own choice.
+    if let Some(video_driver) = &video_driver {
+        sdl2::hint::set("SDL_VIDEODRIVER", video_driver);
+    }
+
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    if verbose {
+        println!("Video driver: {}", video_subsystem.current_video_driver());
+    }
+
+    // The bounds of the display the window was asked to open on. Otherwise, a new window is
+    // centered on the primary display. SDL's own centering can leave the window straddling
+    // two displays on some multi-monitor setups.
+    let display_bounds = match display {
+        Some(display) => {
+            let count = video_subsystem.num_video_displays()?;
+            if display >= count {
+                return Err(format!(
+                    "Invalid display: \"{}\" but there are only {} displays (counting from 0)",
+                    display, count
+                ));
+            }
+
+            Some(video_subsystem.display_bounds(display)?)
+        }
+        None => video_subsystem.display_bounds(0).ok(),
+    };
+
+    // Size the window for the display's pixel density so that the clock comes out
+    // about the same physical size everywhere. The defaults are for a 96 DPI display.
+    if scale_with_monitor_dpi {
+        match video_subsystem.display_dpi(display.unwrap_or(0)) {
+            Ok((diagonal_dpi, _, _)) => {
+                let scale = dpi_scale(diagonal_dpi);
+                window_width = (WIDTH as f32 * scale) as i32;
+                window_height = (HEIGHT as f32 * scale) as i32;
+            }
+            Err(e) => warn(format!("could not find the display's DPI: {}", e)),
+        }
+    }
+
+    let saved_geometry = match remember_geometry {
+        true => geometry::load().map(|geometry| {
+            let displays: Vec<Rect> = (0..video_subsystem.num_video_displays().unwrap_or(0))
+                .filter_map(|display| video_subsystem.display_bounds(display).ok())
+                .collect();
+            geometry.clamp_to_displays(&displays)
+        }),
+        _ => None,
+    };
+
+    // A tiled window goes against the edge of the display, leaving out any
+    // space the desktop keeps for itself like a taskbar or a menu bar.
+    let tiled_geometry = match tile {
+        Some(edge) => Some(geometry::tile(
+            edge,
+            video_subsystem.display_usable_bounds(display.unwrap_or(0))?,
+        )),
+        None => None,
+    };
+
+    if let Some(geometry) = tiled_geometry.or(saved_geometry) {
+        window_width = geometry.width as i32;
+        window_height = geometry.height as i32;
+    }
+
+    let mut window_builder =
+        video_subsystem.window("timer", window_width as u32, window_height as u32);
+    // Tiling wins over a display that was asked for, which wins over wherever the window was left
+    let centered = display_bounds.map(|bounds| center_on(bounds, window_width, window_height));
+    match (tiled_geometry, display, saved_geometry, centered) {
+        (Some(geometry), _, _, _) => window_builder.position(geometry.x, geometry.y),
+        (None, Some(_), _, Some((x, y))) => window_builder.position(x, y),
+        (None, _, Some(geometry), _) => window_builder.position(geometry.x, geometry.y),
+        (None, _, None, Some((x, y))) => window_builder.position(x, y),
+        (None, _, None, None) => window_builder.position_centered(),
+    };
+
+    if borderless {
+        window_builder.borderless();
+    }
+
+    if always_on_top {
+        window_builder.always_on_top();
+    }
+
+    // Going fullscreen fills whichever display the window was placed on
+    if fullscreen {
+        window_builder.fullscreen_desktop();
+    }
+
+    let mut window = window_builder
+        .resizable()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(opacity) = opacity {
+        if let Err(e) = window.set_opacity(opacity) {
+            warn(format!("could not set the window's opacity: {}", e));
+        }
+    }
+
+    let ttf_handler = ttf::init().unwrap();
+    let font = ttf_handler
+        .load_font_from_rwops(RWops::from_bytes(FONT_BYTES).unwrap(), FONT_POINT_SIZE)?;
+
+    // With an outline set, SDL_ttf draws just the stroke around each glyph and leaves the inside
+    // clear. The stroke only adds a few pixels to each side, so the metrics the DVD bounce uses
+    // are still close enough. Only the time is hollow, so it gets a font of its own and
+    // everything else around it is drawn as usual.
+    let hollow_font = match hollow {
+        true => {
+            let mut hollow_font = ttf_handler
+                .load_font_from_rwops(RWops::from_bytes(FONT_BYTES).unwrap(), FONT_POINT_SIZE)?;
+            hollow_font.set_outline_width(
+                ((FONT_POINT_SIZE as f32 * HOLLOW_OUTLINE_FRACTION) as u16).max(1),
+            );
+            Some(hollow_font)
+        }
+        _ => None,
+    };
+    let time_font = hollow_font.as_ref().unwrap_or(&font);
+
+    // Not every font carries glyphs for the other numeral systems,
+    // so fall back to ASCII digits rather than rendering boxes.
+    if numerals != Numerals::Ascii
+        && translate_digits("0123456789", numerals)
+            .chars()
+            .any(|c| font.find_glyph(c).is_none())
+    {
+        warn(format!(
+            "the font is missing glyphs for {:?} numerals; using ASCII digits",
+            numerals
+        ));
+        numerals = Numerals::Ascii;
+    }
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+
+    let _image_context = match background_image {
+        Some(_) => Some(sdl2::image::init(
+            sdl2::image::InitFlag::PNG | sdl2::image::InitFlag::JPG,
+        )?),
+        None => None,
+    };
+    let background_texture = match &background_image {
+        Some(path) => Some(
+            texture_creator
+                .load_texture(path)
+                .map_err(|e| format!("Could not load the background image \"{}\": {}", path, e))?,
+        ),
+        None => None,
+    };
+
+    canvas.set_draw_color(background_color);
+    canvas.clear();
+    canvas.present();
+
+    // The timer keeps counting while minimized, and the window flashing when
+    // it finishes draws attention back to it in the taskbar or dock.
+    if minimized {
+        canvas.window_mut().minimize();
+    }
+
+    // Sound is a nice to have, so a machine without a working
+    // audio device still gets the timer, just without the beeps.
+    // A device that was asked for by name has to be there, rather
+    // than the beeps quietly going to some other device.
+    let audio_device = match &audio_device {
+        Some(name) => {
+            let devices = audio::playback_devices(&sdl_context.audio()?);
+            Some(audio::find_device(&devices, name)?.to_string())
+        }
+        None => None,
+    };
+
+    let mut beeper = match countdown_beeps.is_some()
+        || preroll_beeps
+        || alarm_fade_in.is_some()
+        || (metronome.is_some() && metronome_click)
+    {
+        true => sdl_context
+            .audio()
+            .and_then(|audio_subsystem| {
+                audio::Beeper::open(&audio_subsystem, audio_device.as_deref())
+            })
+            .map_err(|e| warn(format!("could not open an audio device: {}", e)))
+            .ok(),
+        _ => None,
+    };
+
+    // The rest of the preroll beeps play as each second passes, but
+    // the first number is already on screen before any time has passed.
+    if let (Some(beeper), true) = (beeper.as_mut(), preroll_beeps && preroll > 0) {
+        beeper.beep(audio::Beep::Tick);
+    }
+
+    let mut event_pump = sdl_context.event_pump().unwrap();
+    let mouse = sdl_context.mouse();
+    let mut last_mouse_motion = Instant::now();
+    let mut cursor_hidden = false;
+    let mut last_frame = Instant::now();
+
+    let mut laps = laps::Laps::new();
+
+    // With `--confirm-quit`, the first attempt to quit only asks for a second one.
+    // This is when it was asked, along with the prompt to show until then.
+    let mut pending_quit: Option<(Instant, String)> = None;
+
+    // When each digit last changed, for the `--flip-clock` animation
+    let mut digit_roll = roll::DigitRoll::new();
+    let mut glyph_textures: HashMap<(char, Color), Texture> = HashMap::new();
+
+    // A running average of how long each frame takes, for `--show-fps`
+    let mut average_frame_time: f32 = 0.0;
+
+    // The instant the timer ran out. The finished blink is derived from
+    // this so it stays at a steady half second regardless of frame rate.
+    let mut finished_at: Option<Instant> = None;
+
+    // Whether the user has acknowledged the finished timer with a
+    // key press or a click. Some finish effects run until this is set.
+    let mut dismissed = false;
+
+    // The last time the user pressed a key or used the mouse
+    let mut last_interaction = Instant::now();
+
+    // SDL sends a `FocusGained` when the window first opens, so only
+    // a focus that follows a `FocusLost` should restart the timer.
+    let mut lost_focus = false;
+
+    // Whether the timer was paused by `--pause-on-blur`, rather than by the user.
+    // Only a pause from losing focus is undone when the focus comes back.
+    let mut paused_by_blur = false;
+
+    // Whether any of the window can be seen. Drawing is skipped while it's hidden or minimized.
+    let mut visible = true;
+
+    // The system is only kept awake while the countdown is actually running,
+    // so a timer that's left paused doesn't stop it from going to sleep
+    let mut sleep_inhibitor = match inhibit_sleep && !state.paused {
+        true => inhibit::inhibit_sleep(),
+        _ => None,
+    };
+    let mut was_paused = state.paused;
+
+    if announce_start {
+        let label = label_for_round(&labels, round).unwrap_or("Timer");
+
+        // Unlike the finish, there's nothing lost if the start isn't announced
+        if let Err(e) = notification::send(
+            label,
+            &notification_body(&start_template, label, state.initial),
+        ) {
+            warn(format!("could not send the notification: {}", e));
+        }
+    }
+
+    // With `--grace`, the finish notification waits here until the grace
+    // period is over, and is dropped if the timer is revived before then.
+    let mut pending_notification: Option<notification::PendingNotification> = None;
+
+    // With `--nag` or `--repeat-notification`, how many reminders have gone out since the timer
+    // finished, and when the last one (or the finish itself) was.
+    let mut nags_sent = 0;
+    let mut last_nag = Instant::now();
+
+    // With `--rpc`, the commands read from stdin come in here
+    let commands = rpc.then(rpc::read_commands);
+
+    // With `--stopwatches`, each of the stopwatches that the number keys start and stop
+    let mut stopwatches = stopwatch_count.map(|count| stopwatches::Stopwatches::new(count, &clock));
+
+    // With `--chess`, the clocks for each side of the game
+    let mut chess = chess.map(|(time, increment)| chess::ChessClock::new(time, increment, &clock));
+    let mut flagged = false;
+
+    // The `--cue` text to show as the countdown reaches each one
+    let mut cues = cues::Cues::new(cues);
+
+    // Whether another run has been queued up with the key, which puts the number
+    // of queued timers on screen
+    let mut queued_by_key = false;
+
+    // When the current round started, for the `--history` file
+    let mut started_at = chrono::Local::now().to_rfc3339();
+
+    // When the timer opened, for `--max-runtime`. This goes by the wall clock,
+    // so pausing or restarting the timer doesn't buy it any more time.
+    let opened_at = Instant::now();
+
+    'main_loop: loop {
+        let active_timer = state.is_active();
+        if !active_timer && finished_at.is_none() {
+            canvas
+                .window_mut()
+                .flash(sdl2::video::FlashOperation::UntilFocused)?;
+            finished_at = Some(Instant::now());
+            nags_sent = 0;
+            last_nag = Instant::now();
+
+            if queued_timers.is_empty() {
+                sleep_inhibitor = None;
+            }
+
+            let label = label_for_round(&labels, round).unwrap_or("Timer");
+            let body = notification_body(&notify_template, label, state.initial);
+
+            if rpc {
+                rpc::emit(serde_json::json!({ "event": "finished", "round": round }));
+            }
+
+            match grace {
+                Some(grace) => {
+                    // An earlier round can't be brought back now that this one has finished,
+                    // so its notification goes out now rather than being replaced
+                    if let Some(pending) = pending_notification
+                        .take()
+                        .filter(|pending| !pending.is_for(round))
+                    {
+                        if let Err(e) = notification::send(&pending.summary, &pending.body) {
+                            warn(format!("could not send the notification: {}", e));
+                        }
+                    }
+
+                    pending_notification = Some(notification::PendingNotification::new(
+                        label.to_string(),
+                        body,
+                        round,
+                        Instant::now() + std::time::Duration::from_secs_f64(grace),
+                    ))
+                }
+                None => {
+                    if let Err(e) = notification::send(label, &body) {
+                        warn(format!("could not send the notification: {}", e));
+                    }
+                }
+            }
+
+            if let Some(history) = &history {
+                history::record(
+                    history,
+                    &started_at,
+                    state.initial,
+                    label_for_round(&labels, round).unwrap_or(""),
+                    state.mode,
+                );
+            }
+
+            // Move straight on to the next queued timer, if there is one
+            if let Some(next_timer) = queued_timers.pop_front() {
+                state.restart(next_timer, &clock);
+                finished_at = None;
+                round += 1;
+                started_at = chrono::Local::now().to_rfc3339();
+            }
+        }
+
+        // Keep reminding until the finished timer is dismissed
+        if (nag || repeat_notification.is_some())
+            && finished_at.is_some()
+            && !dismissed
+            && reminder_due(
+                nags_sent,
+                last_nag.elapsed().as_secs_f64(),
+                repeat_notification,
+            )
+        {
+            let label = label_for_round(&labels, round).unwrap_or("Timer");
+
+            if let Err(e) = notification::send(
+                label,
+                &notification_body(&notify_template, label, state.initial),
+            ) {
+                warn(format!("could not send the notification: {}", e));
+            }
+            canvas
+                .window_mut()
+                .flash(sdl2::video::FlashOperation::UntilFocused)?;
+
+            nags_sent += 1;
+            last_nag = Instant::now();
+        }
+
+        if let Some(pending) = &pending_notification {
+            match pending.check(Instant::now(), round, state.is_active()) {
+                notification::Pending::Waiting => {}
+                notification::Pending::Cancelled => pending_notification = None,
+                notification::Pending::Due => {
+                    if let Err(e) = notification::send(&pending.summary, &pending.body) {
+                        warn(format!("could not send the notification: {}", e));
+                    }
+                    pending_notification = None;
+                }
+            }
+        }
+
+        // Commands from `--rpc` are handled once a frame, alongside the window's events
+        if let Some(commands) = &commands {
+            for command in commands.try_iter() {
+                match command {
+                    Ok(rpc::Command::Set(seconds)) => {
+                        state.restart(seconds, &clock);
+                        finished_at = None;
+                        dismissed = false;
+                    }
+                    Ok(rpc::Command::Pause) => state.set_paused(true, &clock),
+                    Ok(rpc::Command::Resume) => state.set_paused(false, &clock),
+                    Ok(rpc::Command::Restart) => {
+                        state.restart(state.initial, &clock);
+                        finished_at = None;
+                        dismissed = false;
+                    }
+                    Ok(rpc::Command::Quit) => break 'main_loop,
+                    Err(e) => rpc::emit(serde_json::json!({ "event": "error", "message": e })),
+                }
+
+                if inhibit_sleep && state.is_active() && !state.paused && sleep_inhibitor.is_none()
+                {
+                    sleep_inhibitor = inhibit::inhibit_sleep();
+                }
+            }
+        }
+
+        /****************************
+         *** POLL EVENTS ************
+         ****************************/
+
+        if hide_cursor
+            && !cursor_hidden
+            && last_mouse_motion.elapsed().as_secs_f64() > CURSOR_IDLE_SECONDS
+        {
+            mouse.show_cursor(false);
+            cursor_hidden = true;
+        }
+
+        if runtime_exceeded(max_runtime, opened_at.elapsed().as_secs_f64()) {
+            let label = label_for_round(&labels, round).unwrap_or("Timer");
+
+            if let Err(e) = notification::send(
+                label,
+                &notification_body(
+                    notification::MAX_RUNTIME_TEMPLATE,
+                    label,
+                    max_runtime.unwrap_or(0.0),
+                ),
+            ) {
+                warn(format!("could not send the notification: {}", e));
+            }
+
+            break 'main_loop;
+        }
+
+        if let Some(auto_quit_after) = auto_quit_after {
+            if dismissed && last_interaction.elapsed().as_secs_f64() >= auto_quit_after {
+                break 'main_loop;
+            }
+        }
+
+        // With `--lock`, there's no quitting until the countdown is done
+        let locked = lock && state.mode == TimerMode::Default && finished_at.is_none();
+
+        for event in event_pump.poll_iter() {
+            if let Event::KeyDown { .. }
+            | Event::MouseButtonDown { .. }
+            | Event::MouseMotion { .. } = event
+            {
+                last_interaction = Instant::now();
+            }
+
+            match event {
+                Event::Quit { .. } if locked => {
+                    pending_quit = Some((
+                        Instant::now(),
+                        "Locked until the timer finishes".to_string(),
+                    ));
+                }
+                Event::Quit { .. } => {
+                    if !confirm_quit || quit_confirmed(&pending_quit, Instant::now()) {
+                        break 'main_loop;
+                    }
+
+                    pending_quit = Some((Instant::now(), "Close again to quit".to_string()));
+                }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } => {
+                    dismissed |= finished_at.is_some();
+
+                    if let (Some(stopwatches), Some(index)) = (
+                        stopwatches.as_mut(),
+                        stopwatches::stopwatch_for_key(keycode),
+                    ) {
+                        stopwatches.toggle(index, &clock);
+                        continue;
+                    }
+
+                    if let Some(chess) = chess.as_mut().filter(|_| keycode == Keycode::Space) {
+                        chess.switch(&clock);
+                        continue;
+                    }
+
+                    match key_bindings.get(&keycode) {
+                        Some(Action::Quit) if locked => {
+                            pending_quit = Some((
+                                Instant::now(),
+                                "Locked until the timer finishes".to_string(),
+                            ));
+                        }
+                        Some(Action::Quit) => {
+                            if !confirm_quit || quit_confirmed(&pending_quit, Instant::now()) {
+                                break 'main_loop;
+                            }
+
+                            pending_quit = Some((
+                                Instant::now(),
+                                format!("Press {} again to quit", keycode.name()),
+                            ));
+                        }
+                        Some(Action::Pause) => state.toggle_pause(&clock),
+                        Some(Action::Lap) => laps.record(state.elapsed(&clock)),
+                        // A stopwatch has no duration to run again
+                        Some(Action::QueueAgain) if state.mode == TimerMode::Default => {
+                            queued_by_key = true;
+
+                            // Once everything has finished, the new run starts straight away
+                            match finished_at.is_some() && queued_timers.is_empty() {
+                                true => {
+                                    state.restart(state.initial, &clock);
+                                    finished_at = None;
+                                    dismissed = false;
+                                    round += 1;
+                                    started_at = chrono::Local::now().to_rfc3339();
+
+                                    if inhibit_sleep && sleep_inhibitor.is_none() {
+                                        sleep_inhibitor = inhibit::inhibit_sleep();
+                                    }
+                                }
+                                _ => queued_timers.push_back(state.initial),
+                            }
+                        }
+                        Some(Action::QueueAgain) => {}
+                        Some(Action::CycleTheme) => {
+                            let next = theme::next_theme(theme);
+                            text_color = theme::THEMES[next].text;
+                            background_color = theme::THEMES[next].background;
+                            theme = Some(next);
+                        }
+                        None => {}
+                    }
+                }
+                Event::MouseButtonDown { x, y, .. } => {
+                    let bar = progress_bar_rect(window_width, window_height);
+
+                    // Clicking the progress bar seeks the countdown to that point. Seeking
+                    // back from the finish makes the timer count again, like a restart.
+                    if progress_bar
+                        && state.mode == TimerMode::Default
+                        && bar.contains_point((x, y))
+                    {
+                        state.seek(seek_time(x, bar, state.initial), &clock);
+
+                        if state.is_active() && finished_at.is_some() {
+                            finished_at = None;
+                            dismissed = false;
+
+                            if inhibit_sleep && sleep_inhibitor.is_none() {
+                                sleep_inhibitor = inhibit::inhibit_sleep();
+                            }
+                        }
+                    } else {
+                        dismissed |= finished_at.is_some();
+                    }
+                }
+                Event::MouseMotion { .. } => {
+                    last_mouse_motion = Instant::now();
+
+                    if cursor_hidden {
+                        mouse.show_cursor(true);
+                        cursor_hidden = false;
+                    }
+                }
+                Event::Window { win_event, .. } => match win_event {
+                    WindowEvent::Resized(w, h) => {
+                        window_width = w;
+                        window_height = h;
+                    }
+                    WindowEvent::FocusLost => {
+                        lost_focus = true;
+
+                        if pause_on_blur && state.is_active() && !state.paused {
+                            state.set_paused(true, &clock);
+                            paused_by_blur = true;
+                        }
+                    }
+                    WindowEvent::FocusGained => {
+                        // Coming back to the window means the repeated notification was seen
+                        if repeat_notification.is_some() {
+                            dismissed |= finished_at.is_some();
+                        }
+
+                        if paused_by_blur {
+                            state.set_paused(false, &clock);
+                            paused_by_blur = false;
+                        }
+
+                        // With `--start-paused`, the reset timer waits to be started again
+                        if restart_on_focus && lost_focus {
+                            state.reset(start_paused, &clock);
+                            finished_at = None;
+                            dismissed = false;
+
+                            if inhibit_sleep && !state.paused && sleep_inhibitor.is_none() {
+                                sleep_inhibitor = inhibit::inhibit_sleep();
+                            }
+                        }
+
+                        lost_focus = false;
+                    }
+                    WindowEvent::Hidden | WindowEvent::Minimized => visible = false,
+                    WindowEvent::Shown
+                    | WindowEvent::Exposed
+                    | WindowEvent::Restored
+                    | WindowEvent::Maximized => visible = true,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        /****************************
+         *** RENDER ******************
+         ****************************/
+
+        // The timer was last updated at the end of the previous frame, so this
+        // adds on the time since then. This way the hundredths shown are as close
+        // as possible to the moment the frame is drawn, rather than a frame behind.
+        let display_timer = state.display_time(&clock);
+
+        // The preroll counts down whole seconds before the timer starts
+        let display_preroll = state.display_preroll(&clock);
+
+        let formatted_timer = match (&message, finished_at, unit_seconds) {
+            _ if display_preroll > 0.0 => {
+                translate_digits(&(display_preroll.ceil() as u64).to_string(), numerals)
+            }
+            (Some(message), Some(_), _) => message.clone(),
+            (_, _, Some(unit_seconds)) => {
+                let countdown = state.mode == TimerMode::Default;
+                let count = count_units(display_timer, unit_seconds, countdown);
+                let formatted = match countdown {
+                    true => format!("{} {} remaining", count, unit_label),
+                    _ => format!("{} {}", count, unit_label),
+                };
+                translate_digits(&formatted, numerals)
+            }
+            _ => translate_digits(&format_timer(display_timer, time_format), numerals),
+        };
+
+        // A mirror shows the other timer's time in place of its own
+        if let Some(mirror_file) = mirror_file.as_mut() {
+            mirror_file.poll();
+        }
+        let formatted_timer = match &mirror_file {
+            Some(mirror_file) => translate_digits(mirror_file.text(), numerals),
+            None => formatted_timer,
+        };
+
+        // Once the timer finishes, the final time goes out right away
+        if let Some(time_file) = time_file.as_mut() {
+            time_file.write(&formatted_timer, !active_timer);
+        }
+
+        if flip_clock {
+            digit_roll.update(&formatted_timer, Instant::now());
+        }
+
+        // While the window is hidden or minimized there's nothing to draw. The timer
+        // still runs off the clock, so it's right again as soon as the window comes back.
+        if visible {
+            // A paused timer keeps its usual colors, so the gray text still reads as paused
+            let inverted = state.mode == TimerMode::Default
+                && active_timer
+                && !state.paused
+                && display_preroll <= 0.0
+                && invert_colors(invert_final, display_timer);
+            let (text_color, background_color) = match inverted {
+                true => (background_color, text_color),
+                _ => (text_color, background_color),
+            };
+
+            let grayed_out = mirror_file
+                .as_ref()
+                .map_or(state.paused, |mirror_file| mirror_file.is_stale());
+            let font_color = match (grayed_out, outline_color.filter(|_| hollow)) {
+                (true, _) => Color::RGB(120, 120, 120),
+                (_, Some(outline_color)) => outline_color,
+                _ => text_color,
+            };
+
+            // The separate clocks of `--stopwatches` or `--chess`, each with its own text and color.
+            // A clock that isn't running is grayed out, and a side that's out of time blinks red.
+            let columns: Option<Vec<(String, Color)>> = match (&stopwatches, &chess) {
+                (Some(stopwatches), _) => Some(
+                    stopwatches
+                        .times(&clock)
+                        .enumerate()
+                        .map(|(i, (time, running))| {
+                            let color = match running {
+                                true => font_color,
+                                _ => Color::RGB(120, 120, 120),
+                            };
+                            // Each stopwatch is numbered to match its key
+                            (
+                                format!("{}  {}", i + 1, format_timer(time, time_format)),
+                                color,
+                            )
+                        })
+                        .collect(),
+                ),
+                (_, Some(chess)) => Some(
+                    chess
+                        .times(&clock)
+                        .enumerate()
+                        .map(|(i, (time, turn))| {
+                            let blink = opened_at.elapsed().as_secs_f64() % 1.0 < 0.5;
+                            let color = match (chess.flagged() == Some(i), turn) {
+                                (true, _) if blink => FLASH_SCREEN_COLOR,
+                                (true, _) => background_color,
+                                (_, true) => font_color,
+                                _ => Color::RGB(120, 120, 120),
+                            };
+                            (format_timer(time, time_format), color)
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            };
+
+            let pre_texture = time_font
+                .render(&formatted_timer)
+                .solid(font_color)
+                .unwrap();
+            let mut texture = pre_texture.as_texture(&texture_creator).unwrap();
+
+            // Right after the timer finishes, the background alternates with the
+            // flash color for a short while before settling back into the blink.
+            let screen_flash = finished_at.is_some_and(|finished_at| {
+                let elapsed = finished_at.elapsed().as_secs_f64();
+                flash_screen
+                    && !static_finish
+                    && elapsed < FLASH_SCREEN_DURATION
+                    && elapsed % (FLASH_SCREEN_INTERVAL * 2.0) < FLASH_SCREEN_INTERVAL
+            });
+
+            // The alert flash keeps going at the requested rate until it's dismissed
+            let alert_flash_on = match (alert_flash, finished_at) {
+                (Some(hz), Some(finished_at)) if !dismissed => {
+                    (finished_at.elapsed().as_secs_f64() * hz) % 1.0 < 0.5
+                }
+                _ => false,
+            };
+
+            match (alert_flash_on, screen_flash) {
+                (true, _) => {
+                    canvas.set_draw_color(color::blend(background_color, alert_color));
+                    canvas.clear();
+                }
+                (_, true) => {
+                    canvas.set_draw_color(FLASH_SCREEN_COLOR);
+                    canvas.clear();
+                }
+                // Rather than clearing the last frame, the trail covers it with a see through
+                // layer of the background so that each earlier position fades out over a few frames.
+                _ if dvd_trail && display_mode == DisplayMode::DVD => {
+                    canvas.set_blend_mode(BlendMode::Blend);
+                    canvas.set_draw_color(Color::RGBA(
+                        background_color.r,
+                        background_color.g,
+                        background_color.b,
+                        DVD_TRAIL_FADE,
+                    ));
+                    canvas.fill_rect(None)?;
+                    canvas.set_blend_mode(BlendMode::None);
+                }
+                _ => {
+                    canvas.set_draw_color(background_color);
+                    canvas.clear();
+
+                    if let Some(background_texture) = &background_texture {
+                        let query = background_texture.query();
+                        match background_tile {
+                            true => {
+                                for tile in tile_rects(
+                                    query.width,
+                                    query.height,
+                                    window_width,
+                                    window_height,
+                                ) {
+                                    canvas.copy(background_texture, None, tile)?;
+                                }
+                            }
+                            _ => canvas.copy(
+                                background_texture,
+                                None,
+                                cover_rect(query.width, query.height, window_width, window_height),
+                            )?,
+                        }
+                    }
+                }
+            }
+
+            match display_mode {
+                DisplayMode::DVD => {
+                    let width = (window_width as f32 * DVD_FONT_SCALE) as u32;
+                    let height = (window_height as f32 * DVD_FONT_SCALE) as u32;
+
+                    // The font has some padding above it. To make the timer properly hit the top of
+                    // the window by ignoring the padding, we need to calculate the space between
+                    // the font ascent and the font's top. This will give us the padding value.
+                    let font_padding_above_ascent_percentage =
+                        (font.height() - font.ascent()) as f32 / font.height() as f32;
+
+                    // There is also some padding under the font's baseline which makes the bounce
+                    // occur earlier than it should. Here we'll take the baseline and add it to
+                    // `y` on the timer_display to find where the true bottom of the text is.
+                    //
+                    // NOTE: If we were to ever add characters that go below
+                    // baseline, then the bounce effect would break since
+                    // we're calcluating the bounce from the baseline.
+                    let font_height_from_baseline_percentage =
+                        (font.height() + font.descent()) as f32 / font.height() as f32;
+
+                    timer_display.width = width;
+                    timer_display.height = height;
+                    bounce(
+                        &mut timer_display,
+                        window_width,
+                        window_height,
+                        font_padding_above_ascent_percentage,
+                        font_height_from_baseline_percentage,
+                    );
+
+                    // By the first frame drawn, the window has taken on its real size
+                    if other_logos.len() + 1 < dvd_logos {
+                        other_logos =
+                            seed_logos(dvd_logos, window_width, window_height, width, height);
+                    }
+
+                    // Like the DVD logo, the others change color each time they hit an edge
+                    for (logo, color) in other_logos.iter_mut() {
+                        logo.width = width;
+                        logo.height = height;
+
+                        if bounce(
+                            logo,
+                            window_width,
+                            window_height,
+                            font_padding_above_ascent_percentage,
+                            font_height_from_baseline_percentage,
+                        ) {
+                            *color = (*color + 1) % DVD_COLORS.len();
+                        }
+                    }
+                }
+                DisplayMode::Default => {
+                    // Calculate the time display based on the window width and
+                    // height. We run this every frame just in case the user
+                    // has resized the window which changes the font size.
+                    timer_display.x = (window_width as f32 * padding) as i32;
+                    timer_display.y = (window_height as f32 * padding) as i32;
+                    timer_display.width = (window_width as f32 * text_size) as u32;
+                    timer_display.height = (window_height as f32 * text_size) as u32;
+                }
+            }
+
+            // A preroll number is drawn big in the middle of where the time goes,
+            // keeping its proportions rather than being stretched to the full width.
+            if display_preroll > 0.0 && display_mode == DisplayMode::Default {
+                let query = texture.query();
+                let width = (timer_display.height as f32 * query.width as f32
+                    / query.height.max(1) as f32) as u32;
+                timer_display.x = (window_width - width as i32) / 2;
+                timer_display.width = width;
+            }
+
+            // The text only takes its share of the reserved width, centered in it
+            if let Some(min_width_chars) = min_width_chars
+                .filter(|_| display_mode == DisplayMode::Default && display_preroll <= 0.0)
+            {
+                let glyph_width = time_font.size_of_char('0').map_or(0, |(width, _)| width);
+                let fraction =
+                    reserved_fraction(texture.query().width, glyph_width, min_width_chars);
+                let width = (timer_display.width as f32 * fraction) as u32;
+                timer_display.x += (timer_display.width - width) as i32 / 2;
+                timer_display.width = width;
+            }
+
+            if smooth_resize {
+                // Move a fraction of the remaining distance based on how long
+                // the frame took, so the easing feels the same at any frame rate.
+                let step = 1.0 - (-last_frame.elapsed().as_secs_f32() * SMOOTH_RESIZE_SPEED).exp();
+                let (target_width, target_height) =
+                    (timer_display.width as f32, timer_display.height as f32);
+                let (width, height) =
+                    smoothed_size.map_or((target_width, target_height), |(w, h)| {
+                        (
+                            w + (target_width - w) * step,
+                            h + (target_height - h) * step,
+                        )
+                    });
+                smoothed_size = Some((width, height));
+
+                timer_display.width = width as u32;
+                timer_display.height = height as u32;
+
+                // Keep the easing text centered in the window
+                if display_mode == DisplayMode::Default {
+                    timer_display.x = (window_width - width as i32) / 2;
+                    timer_display.y = (window_height - height as i32) / 2;
+                }
+            }
+
+            // The zoom is centered in the window, and stops short of growing past its edges
+            if zoom_final
+                && display_mode == DisplayMode::Default
+                && state.mode == TimerMode::Default
+                && active_timer
+                && display_preroll <= 0.0
+            {
+                let scale = zoom_final_scale(display_timer)
+                    .min(window_width as f32 / timer_display.width.max(1) as f32)
+                    .min(window_height as f32 / timer_display.height.max(1) as f32)
+                    .max(1.0);
+                timer_display.width = (timer_display.width as f32 * scale) as u32;
+                timer_display.height = (timer_display.height as f32 * scale) as u32;
+                timer_display.x = (window_width - timer_display.width as i32) / 2;
+                timer_display.y = (window_height - timer_display.height as i32) / 2;
+            }
+
+            // Scale the text back up to the minimum size, keeping its proportions so
+            // it doesn't distort. Anything that no longer fits is clipped by the window.
+            if let Some(min_font_size) = min_font_size {
+                if timer_display.height < min_font_size {
+                    let scale = min_font_size as f32 / timer_display.height.max(1) as f32;
+                    timer_display.width = (timer_display.width as f32 * scale) as u32;
+                    timer_display.height = min_font_size;
+
+                    match display_mode {
+                        DisplayMode::Default => {
+                            timer_display.x = (window_width - timer_display.width as i32) / 2;
+                            timer_display.y = (window_height - timer_display.height as i32) / 2;
+                        }
+                        // When the text is bigger than the window there's nothing to bounce
+                        // between, so pin it to the edge instead of letting it drift away.
+                        DisplayMode::DVD => {
+                            if timer_display.width as i32 >= window_width {
+                                timer_display.x = 0;
+                            }
+
+                            if timer_display.height as i32 >= window_height {
+                                timer_display.y = 0;
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Once `active_timer` is false, we flash the completed
+            // timer on the screen every half second; so we need
+            // to set `flash_timer` every half second for it.
+            let flash_timer = static_finish
+                || finished_at
+                    .is_none_or(|finished_at| finished_at.elapsed().as_secs_f64() % 1.0 < 0.5);
+
+            if active_timer || flash_timer {
+                let area = Rect::new(
+                    timer_display.x,
+                    timer_display.y,
+                    timer_display.width,
+                    timer_display.height,
+                );
+
+                // The flip clock draws each glyph on its own, and gives each one its own shadow
+                let rolling = flip_clock
+                    && !(lcd && segments::can_draw(&formatted_timer))
+                    && columns.is_none()
+                    && !(show_both && state.mode == TimerMode::Default && display_preroll <= 0.0)
+                    && !vertical;
+
+                // The shadow is the same texture tinted with the shadow color,
+                // drawn first and nudged down and to the right of the text.
+                if shadow && !rolling {
+                    let offset = (timer_display.height as f32 * shadow_offset) as i32;
+                    texture.set_blend_mode(BlendMode::Blend);
+                    texture.set_color_mod(shadow_color.r, shadow_color.g, shadow_color.b);
+                    texture.set_alpha_mod(shadow_color.a);
+
+                    canvas
+                        .copy_ex(
+                            &texture,
+                            None,
+                            Rect::new(
+                                timer_display.x + offset,
+                                timer_display.y + offset,
+                                timer_display.width,
+                                timer_display.height,
+                            ),
+                            0.0,
+                            None,
+                            mirror,
+                            mirror_vertical,
+                        )
+                        .expect("Error writing texture");
+
+                    texture.set_color_mod(255, 255, 255);
+                    texture.set_alpha_mod(255);
+                }
+
+                if lcd && segments::can_draw(&formatted_timer) {
+                    // The unlit segments show faintly behind the lit ones, like on a real display
+                    let (lit, unlit) = segments::layout(&formatted_timer, area);
+
+                    canvas.set_draw_color(color::blend(
+                        background_color,
+                        Color::RGBA(font_color.r, font_color.g, font_color.b, LCD_UNLIT_ALPHA),
+                    ));
+                    canvas.fill_rects(&unlit)?;
+                    canvas.set_draw_color(font_color);
+                    canvas.fill_rects(&lit)?;
+                } else if let Some(columns) = &columns {
+                    let parts: Vec<(String, Color)> = columns
+                        .iter()
+                        .map(|(text, color)| (translate_digits(text, numerals), *color))
+                        .collect();
+
+                    for (texture, rect) in
+                        render_parts(time_font, &texture_creator, &parts, vertical, area)?
+                    {
+                        canvas.copy(&texture, None, rect)?;
+                    }
+                } else if show_both && state.mode == TimerMode::Default && display_preroll <= 0.0 {
+                    // The elapsed time comes first, then the time remaining
+                    let parts: Vec<(String, Color)> = [
+                        format!(
+                            "{} elapsed",
+                            format_timer(state.elapsed(&clock), time_format)
+                        ),
+                        format!("{} remaining", format_timer(display_timer, time_format)),
+                    ]
+                    .iter()
+                    .map(|part| (translate_digits(part, numerals), font_color))
+                    .collect();
+
+                    for (texture, rect) in
+                        render_parts(time_font, &texture_creator, &parts, vertical, area)?
+                    {
+                        canvas.copy(&texture, None, rect)?;
+                    }
+                } else if vertical {
+                    // Each part of the time gets its own row, one under the other
+                    let parts: Vec<(String, Color)> = formatted_timer
+                        .split(':')
+                        .map(|part| (part.to_string(), font_color))
+                        .collect();
+
+                    for (texture, row) in
+                        render_parts(time_font, &texture_creator, &parts, true, area)?
+                    {
+                        canvas.copy(&texture, None, row)?;
+                    }
+                } else if rolling {
+                    // Each character gets a slot as wide as its glyph, scaled to fill the width
+                    // of the text. A changed digit slides up and out of its slot as the new one
+                    // slides in from below, clipped so that neither spills into its neighbours.
+                    // Mirroring flips the order of the slots and the way the digits roll too.
+                    let now = Instant::now();
+                    let widths: Vec<u32> = formatted_timer
+                        .chars()
+                        .map(|c| time_font.size_of_char(c).map_or(0, |(width, _)| width))
+                        .collect();
+                    let scale =
+                        timer_display.width as f32 / widths.iter().sum::<u32>().max(1) as f32;
+                    let height = timer_display.height as i32;
+                    let direction = match mirror_vertical {
+                        true => -1,
+                        _ => 1,
+                    };
+                    let mut x = timer_display.x as f32;
+                    let mut slots = Vec::new();
+
+                    for ((current, previous, progress), width) in digit_roll.frames(now).zip(widths)
+                    {
+                        let slot = mirror_rect(
+                            Rect::new(
+                                x as i32,
+                                timer_display.y,
+                                (width as f32 * scale) as u32,
+                                timer_display.height,
+                            ),
+                            area,
+                            mirror,
+                            mirror_vertical,
+                        );
+                        let offset = (height as f32 * progress) as i32;
+
+                        let mut glyphs = vec![(current, slot.y() + direction * (height - offset))];
+                        if progress < 1.0 {
+                            glyphs.push((previous, slot.y() - direction * offset));
+                        }
+
+                        slots.push((slot, glyphs));
+                        x += width as f32 * scale;
+                    }
+
+                    // The shadows all go down first, so that none of them covers the glyph
+                    // next to it. Each is nudged along with the slot it's clipped to.
+                    let mut passes = Vec::new();
+                    if shadow {
+                        passes.push((
+                            shadow_color,
+                            (timer_display.height as f32 * shadow_offset) as i32,
+                        ));
+                    }
+                    passes.push((Color::RGBA(255, 255, 255, 255), 0));
+
+                    for (tint, nudge) in passes {
+                        for (slot, glyphs) in &slots {
+                            canvas.set_clip_rect(Rect::new(
+                                slot.x() + nudge,
+                                slot.y() + nudge,
+                                slot.width(),
+                                slot.height(),
+                            ));
+
+                            for (c, y) in glyphs {
+                                let glyph_texture = glyph_texture(
+                                    &mut glyph_textures,
+                                    time_font,
+                                    &texture_creator,
+                                    *c,
+                                    font_color,
+                                )?;
+                                glyph_texture.set_color_mod(tint.r, tint.g, tint.b);
+                                glyph_texture.set_alpha_mod(tint.a);
+
+                                canvas.copy_ex(
+                                    glyph_texture,
+                                    None,
+                                    Rect::new(
+                                        slot.x() + nudge,
+                                        y + nudge,
+                                        slot.width(),
+                                        slot.height(),
+                                    ),
+                                    0.0,
+                                    None,
+                                    mirror,
+                                    mirror_vertical,
+                                )?;
+                            }
+                        }
+                        canvas.set_clip_rect(None);
+                    }
+                } else {
+                    // Flipping happens in place, so the text keeps the same spot on screen
+                    canvas
+                        .copy_ex(
+                            &texture,
+                            None,
+                            Rect::new(
+                                timer_display.x,
+                                timer_display.y,
+                                timer_display.width,
+                                timer_display.height,
+                            ),
+                            0.0,
+                            None,
+                            mirror,
+                            mirror_vertical,
+                        )
+                        .expect("Error writing texture");
+                }
+
+                // The other logos all show the same time, so each color
+                // that one of them is showing only needs rendering once
+                let logo_textures = DVD_COLORS
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(i, logo_color)| match other_logos.iter().any(|(_, color)| *color == i) {
+                            true => time_font
+                                .render(&formatted_timer)
+                                .solid(*logo_color)
+                                .map_err(|e| e.to_string())?
+                                .as_texture(&texture_creator)
+                                .map(Some)
+                                .map_err(|e| e.to_string()),
+                            _ => Ok(None),
+                        },
+                    )
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                for (logo, color) in other_logos.iter() {
+                    if let Some(logo_texture) = &logo_textures[*color] {
+                        canvas.copy_ex(
+                            logo_texture,
+                            None,
+                            Rect::new(logo.x, logo.y, logo.width, logo.height),
+                            0.0,
+                            None,
+                            mirror,
+                            mirror_vertical,
+                        )?;
+                    }
+                }
+            }
+
+            // Draw the text's bounding box, a crosshair on its center, and its baseline.
+            // The baseline uses the same font metrics as the DVD bounce calculations.
+            if debug_layout {
+                let rect = Rect::new(
+                    timer_display.x,
+                    timer_display.y,
+                    timer_display.width,
+                    timer_display.height,
+                );
+                let center = rect.center();
+                let crosshair = (timer_display.height / 10) as i32;
+                let baseline = timer_display.y
+                    + (timer_display.height as f32 * font.ascent() as f32 / font.height() as f32)
+                        as i32;
+
+                canvas.set_draw_color(DEBUG_LAYOUT_COLOR);
+                canvas.draw_rect(rect)?;
+                canvas.draw_line(
+                    (center.x() - crosshair, center.y()),
+                    (center.x() + crosshair, center.y()),
+                )?;
+                canvas.draw_line(
+                    (center.x(), center.y() - crosshair),
+                    (center.x(), center.y() + crosshair),
+                )?;
+                canvas.draw_line((rect.left(), baseline), (rect.right(), baseline))?;
+            }
+
+            // The border fades in and out around the finished time until it's dismissed
+            if let (true, Some(finished_at), false) = (border_flash, finished_at, dismissed) {
+                let phase = finished_at.elapsed().as_secs_f64() * BORDER_FLASH_HZ;
+                let pulse = 0.5 - 0.5 * (phase * std::f64::consts::TAU).cos();
+                let thickness = (window_width.min(window_height) as f32 * border_width) as u32;
+
+                canvas.set_draw_color(color::blend(
+                    background_color,
+                    Color::RGBA(
+                        border_color.r,
+                        border_color.g,
+                        border_color.b,
+                        (pulse * 255.0) as u8,
+                    ),
+                ));
+                canvas.fill_rects(&border_rects(window_width, window_height, thickness))?;
+            }
+
+            // A cue sits across the top of the window, and the quit prompt goes over it
+            if let Some(cue) = cues.current(Instant::now()) {
+                let cue_texture = font
+                    .render(cue)
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = cue_texture.query();
+                let cue_height = (window_height as f32 * CUE_SIZE) as u32;
+                let cue_width = u32::min(
+                    (cue_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32,
+                    window_width as u32,
+                );
+
+                canvas
+                    .copy(
+                        &cue_texture,
+                        None,
+                        Rect::new(
+                            (window_width - cue_width as i32) / 2,
+                            0,
+                            cue_width,
+                            cue_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The quit prompt sits across the top of the window until it runs out
+            if let Some((_, prompt)) = pending_quit
+                .as_ref()
+                .filter(|_| quit_confirmed(&pending_quit, Instant::now()))
+            {
+                let prompt_texture = font
+                    .render(prompt)
+                    .solid(text_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = prompt_texture.query();
+                let prompt_height = (window_height as f32 * LABEL_SIZE) as u32;
+                let prompt_width =
+                    (prompt_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32;
+
+                canvas
+                    .copy(
+                        &prompt_texture,
+                        None,
+                        Rect::new(
+                            (window_width - prompt_width as i32) / 2,
+                            0,
+                            prompt_width,
+                            prompt_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The most recent laps are listed under the time, newest first, keeping
+            // clear of the label and the progress bar along the bottom
+            let lap_height = (window_height as f32 * LAP_SIZE) as u32;
+            let mut laps_bottom = window_height;
+            if label_for_round(&labels, round).is_some_and(|label| !label.is_empty()) {
+                laps_bottom -= (window_height as f32 * LABEL_SIZE) as i32;
+            }
+            if progress_bar && state.mode == TimerMode::Default {
+                laps_bottom = laps_bottom.min(progress_bar_rect(window_width, window_height).y());
+            }
+            let laps_top = laps_top(
+                timer_display.y + timer_display.height as i32,
+                laps_bottom,
+                laps.laps().count().min(LAPS_SHOWN),
+                lap_height,
+            );
+            for (i, (number, lap, total)) in laps.laps().rev().take(LAPS_SHOWN).enumerate() {
+                let lap_texture = font
+                    .render(&translate_digits(
+                        &laps::format_lap(number, lap, total, time_format),
+                        numerals,
+                    ))
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = lap_texture.query();
+                let lap_width =
+                    (lap_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32;
+
+                canvas
+                    .copy(
+                        &lap_texture,
+                        None,
+                        Rect::new(
+                            (window_width - lap_width as i32) / 2,
+                            laps_top + (lap_height * i as u32) as i32,
+                            lap_width,
+                            lap_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The progress bar runs along the bottom of the window, filling up as the time passes
+            if progress_bar && state.mode == TimerMode::Default {
+                let bar = progress_bar_rect(window_width, window_height);
+                let fraction = progress_fraction(display_timer, state.initial);
+
+                canvas.set_draw_color(color::blend(
+                    background_color,
+                    Color::RGBA(
+                        font_color.r,
+                        font_color.g,
+                        font_color.b,
+                        PROGRESS_BAR_UNFILLED_ALPHA,
+                    ),
+                ));
+                canvas.fill_rect(bar)?;
+                canvas.set_draw_color(font_color);
+                canvas.fill_rect(Rect::new(
+                    bar.x(),
+                    bar.y(),
+                    (bar.width() as f64 * fraction) as u32,
+                    bar.height(),
+                ))?;
+            }
+
+            // The percentage sits centered just above the time. A stopwatch has no
+            // end to count towards, so there's nothing to show for one.
+            if show_percent && state.mode == TimerMode::Default {
+                let percent = translate_digits(
+                    &format_percent(progress_fraction(display_timer, state.initial)),
+                    numerals,
+                );
+                let percent_texture = font
+                    .render(&percent)
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = percent_texture.query();
+                let percent_height = (window_height as f32 * PERCENT_SIZE) as u32;
+                let percent_width = (percent_height as f32 * query.width as f32
+                    / query.height.max(1) as f32) as u32;
+
+                canvas
+                    .copy(
+                        &percent_texture,
+                        None,
+                        Rect::new(
+                            timer_display.x
+                                + (timer_display.width as i32 - percent_width as i32) / 2,
+                            (timer_display.y - percent_height as i32).max(0),
+                            percent_width,
+                            percent_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The label sits centered under the time, sized to keep its proportions
+            if let Some(label) = label_for_round(&labels, round).filter(|label| !label.is_empty()) {
+                let label_texture = font
+                    .render(label)
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = label_texture.query();
+                let label_height = (window_height as f32 * LABEL_SIZE) as u32;
+                let label_width =
+                    (label_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32;
+
+                canvas
+                    .copy(
+                        &label_texture,
+                        None,
+                        Rect::new(
+                            (window_width - label_width as i32) / 2,
+                            window_height - label_height as i32,
+                            label_width,
+                            label_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The elapsed counter stays put in the bottom left corner. It's
+            // placed relative to the window so that it survives a resize.
+            if display_mode == DisplayMode::DVD && dvd_elapsed {
+                let elapsed = state.elapsed(&clock);
+                let formatted_elapsed = translate_digits(
+                    &format!("+{}", format_timer(elapsed, time_format)),
+                    numerals,
+                );
+                let elapsed_texture = font
+                    .render(&formatted_elapsed)
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let elapsed_height = (window_height as f32 * DVD_ELAPSED_SCALE) as u32;
+
+                canvas
+                    .copy(
+                        &elapsed_texture,
+                        None,
+                        Rect::new(
+                            (window_width as f32 * DVD_ELAPSED_PADDING) as i32,
+                            window_height
+                                - elapsed_height as i32
+                                - (window_height as f32 * DVD_ELAPSED_PADDING) as i32,
+                            (window_width as f32 * DVD_ELAPSED_SCALE) as u32,
+                            elapsed_height,
+                        ),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            // The number of queued timers sits in the top left corner
+            if queued_by_key && !queued_timers.is_empty() {
+                let queue_texture = font
+                    .render(&format!("+{} queued", queued_timers.len()))
+                    .solid(font_color)
+                    .map_err(|e| e.to_string())?
+                    .as_texture(&texture_creator)
+                    .map_err(|e| e.to_string())?;
+                let query = queue_texture.query();
+                let queue_height = (window_height as f32 * QUEUE_OVERLAY_SIZE) as u32;
+                let queue_width =
+                    (queue_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32;
+
+                canvas.copy(
+                    &queue_texture,
+                    None,
+                    Rect::new(0, 0, queue_width, queue_height),
+                )?;
+            }
+
+            // The metronome flashes a square at the top for the start of each beat
+            if let Some(bpm) = metronome {
+                let (_, phase) = beat_position(state.elapsed(&clock), bpm);
+
+                if state.is_active()
+                    && !state.paused
+                    && display_preroll <= 0.0
+                    && phase < METRONOME_FLASH
+                {
+                    let size = ((window_height as f32 * METRONOME_SIZE) as u32).max(1);
+
+                    canvas.set_draw_color(font_color);
+                    canvas.fill_rect(Rect::new(
+                        (window_width - size as i32) / 2,
+                        size as i32 / 2,
+                        size,
+                        size,
+                    ))?;
+                }
+            }
+
+            // The frame rate sits in the top right corner, out of the way of the elapsed counter
+            if show_fps && average_frame_time > 0.0 {
+                let fps_texture = font
+                    .render(&describe_frame_time(average_frame_time))
+                    .solid(font_color)
+                    .unwrap()
+                    .as_texture(&texture_creator)
+                    .unwrap();
+                let query = fps_texture.query();
+                let fps_height = (window_height as f32 * FPS_OVERLAY_SIZE) as u32;
+                let fps_width =
+                    (fps_height as f32 * query.width as f32 / query.height.max(1) as f32) as u32;
+
+                canvas
+                    .copy(
+                        &fps_texture,
+                        None,
+                        Rect::new(window_width - fps_width as i32, 0, fps_width, fps_height),
+                    )
+                    .expect("Error writing texture");
+            }
+
+            canvas.present();
+        } else {
+            // Nothing can be seen, so rather than spinning on frames that are never
+            // drawn, only check in often enough to keep the beeps and notifications on time
+            std::thread::sleep(HIDDEN_FRAME_INTERVAL);
+        }
+
+        /****************************
+         *** UPDATE TIMER ************
+         ****************************/
+
+        let frame_time = last_frame.elapsed().as_secs_f32();
+        average_frame_time = match average_frame_time {
+            0.0 => frame_time,
+            _ => average_frame_time + (frame_time - average_frame_time) * FPS_SMOOTHING,
+        };
+        last_frame = Instant::now();
+        let previous_preroll = state.preroll;
+        let previous_timer = state.update(&clock);
+
+        // Pausing lets go of the inhibitor, and resuming takes out a new one. Dropping
+        // the old one releases its cookie, so toggling quickly never leaves one behind.
+        if inhibit_sleep && state.paused != was_paused {
+            match state.paused {
+                true => sleep_inhibitor = None,
+                _ if state.is_active() && sleep_inhibitor.is_none() => {
+                    sleep_inhibitor = inhibit::inhibit_sleep()
+                }
+                _ => {}
+            }
+        }
+        was_paused = state.paused;
+
+        if let Some(stopwatches) = stopwatches.as_mut() {
+            stopwatches.update(&clock);
+        }
+
+        if let Some(chess) = chess.as_mut() {
+            chess.update(&clock);
+
+            // The flag falling is the end of the game, so it gets the same alert as a finished timer
+            if let (Some(side), false) = (chess.flagged(), flagged) {
+                flagged = true;
+
+                if let Err(e) =
+                    notification::send("Timer", &format!("Side {} is out of time", side + 1))
+                {
+                    warn(format!("could not send the notification: {}", e));
+                }
+                canvas
+                    .window_mut()
+                    .flash(sdl2::video::FlashOperation::UntilFocused)?;
+            }
+        }
+
+        // Each beat clicks as it starts, going by the same clock as the flash
+        if let (Some(beeper), Some(bpm), true) = (beeper.as_mut(), metronome, metronome_click) {
+            let beat = |timer: f64| match state.mode {
+                TimerMode::Default => beat_position(state.initial - timer, bpm).0,
+                TimerMode::Stopwatch => beat_position(timer, bpm).0,
+            };
+
+            if state.preroll <= 0.0 && beat(state.timer) != beat(previous_timer) {
+                beeper.beep(audio::Beep::Click);
+            }
+        }
+
+        if let (Some(beeper), true) = (beeper.as_mut(), preroll_beeps) {
+            if let Some(beep) = audio::countdown_beep(previous_preroll, state.preroll, preroll) {
+                beeper.beep(beep);
+            }
+        }
+
+        if state.mode == TimerMode::Default {
+            cues.update(previous_timer, state.timer, Instant::now());
+
+            for threshold in speak_at
+                .iter()
+                .filter(|threshold| state::crossed(previous_timer, state.timer, **threshold))
+            {
+                speech::speak(&speak_command, &speech::describe_remaining(*threshold));
+            }
+
+            if let Some(seconds) = speak_countdown {
+                if let Some(words) = speech::countdown_words(previous_timer, state.timer, seconds) {
+                    speech::speak(&speak_command, &words);
+                }
+            }
+
+            // Warnings only notify by default, unlike the finish which also flashes
+            for threshold in warn_at
+                .iter()
+                .filter(|threshold| state::crossed(previous_timer, state.timer, **threshold))
+            {
+                let label = label_for_round(&labels, round).unwrap_or("Timer");
+                if let Err(e) = notification::send(label, &speech::describe_remaining(*threshold)) {
+                    warn(format!("could not send the notification: {}", e));
+                }
+
+                if warn_flash {
+                    canvas
+                        .window_mut()
+                        .flash(sdl2::video::FlashOperation::Briefly)?;
+                }
+            }
+        }
+
+        if let (Some(beeper), TimerMode::Default) = (beeper.as_mut(), state.mode) {
+            let beep =
+                audio::countdown_beep(previous_timer, state.timer, countdown_beeps.unwrap_or(0));
+
+            match (beep, alarm_fade_in) {
+                (Some(audio::Beep::Final), Some(fade_in)) => {
+                    beeper.fade_in(audio::Beep::Final, fade_in as f32)
+                }
+                (Some(beep), _) if countdown_beeps.is_some() => beeper.beep(beep),
+                _ => {}
+            }
+        }
+    }
+
+    // Quitting doesn't take back a finish that was still waiting out its grace period
+    if let Some(pending) = pending_notification.filter(|pending| {
+        pending.check(Instant::now(), round, state.is_active()) != notification::Pending::Cancelled
+    }) {
+        if let Err(e) = notification::send(&pending.summary, &pending.body) {
+            warn(format!("could not send the notification: {}", e));
+        }
+    }
+
+    if remember_geometry {
+        let (x, y) = canvas.window().position();
+        let (width, height) = canvas.window().size();
+
+        if let Err(e) = geometry::save(geometry::Geometry {
+            x,
+            y,
+            width,
+            height,
+        }) {
+            warn(e);
+        }
+    }
+
+    if resume && state.mode == TimerMode::Default {
+        resume::save_or_clear(&state, finished_at.is_some(), &clock);
+    }
+
+    // A stopwatch has no finish, so it goes in the history once it's stopped
+    if let (Some(history), TimerMode::Stopwatch) = (&history, state.mode) {
+        history::record(
+            history,
+            &started_at,
+            state.display_time(&clock),
+            label_for_round(&labels, round).unwrap_or(""),
+            state.mode,
+        );
+    }
+
+    if !laps.is_empty() {
+        let lines = laps.to_lines(time_format);
+
+        match &laps_out {
+            Some(path) => std::fs::write(path, lines)
+                .map_err(|e| format!("Could not save the laps to \"{}\": {}", path, e))?,
+            None => print!("{}", lines),
+        }
+    }
+
+    // A stopwatch has nothing to finish, so quitting it is how it's meant to end
+    match finished_at.is_some() || state.mode == TimerMode::Stopwatch {
+        true => Ok(ExitCode::SUCCESS),
+        _ => Ok(ExitCode::from(EXIT_CANCELLED)),
+    }
+}
+
+#[test]
+fn it_should_parse_a_time_with_only_seconds() {
+    assert_eq!(10.0, parse_timer("10").unwrap());
+}
+
+#[test]
+fn it_should_parse_a_time_with_minutes_and_seconds() {
+    assert_eq!(70.0, parse_timer("01:10").unwrap());
+}
+
+#[test]
+fn it_should_parse_a_time_with_hours_minutes_and_seconds() {
+    assert_eq!(3670.0, parse_timer("01:01:10").unwrap());
+}
+
+#[test]
+fn it_should_translate_digits_to_another_numeral_system() {
+    assert_eq!(
+        "\u{0660}\u{0661}:\u{0669}",
+        translate_digits("01:9", Numerals::Arabic)
+    );
+    assert_eq!("12:34.5", translate_digits("12:34.5", Numerals::Ascii));
+}
+
+#[test]
+fn it_should_format_zero() {
+    assert_eq!("00:00:00.00", format_timer(0.0, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_format_a_negative_time_as_zero() {
+    assert_eq!("00:00:00.00", format_timer(-0.01, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_round_the_hundredths() {
+    assert_eq!("00:00:00.01", format_timer(0.005, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_format_the_last_hundredth_before_an_hour() {
+    assert_eq!("00:59:59.99", format_timer(3599.99, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_format_exactly_one_hour() {
+    assert_eq!("01:00:00.00", format_timer(3600.0, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_format_the_last_hundredth_of_a_day() {
+    assert_eq!("23:59:59.99", format_timer(86399.99, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_carry_a_rounded_up_second_into_the_minutes() {
+    assert_eq!("00:01:00.00", format_timer(59.999, TimeFormat::default()));
+}
+
+#[test]
+fn it_should_parse_a_time_with_units() {
+    assert_eq!(300.0, parse_timer("5m").unwrap());
+    assert_eq!(5400.0, parse_timer("1h30m").unwrap());
+    assert_eq!(3725.0, parse_timer("1h2m5s").unwrap());
+    assert_eq!(90.0, parse_timer("1.5m").unwrap());
+}
+
+#[test]
+fn it_should_reject_an_invalid_time() {
+    assert!(parse_timer("ten").is_err());
+    assert!(parse_timer("1:xx").is_err());
+    assert!(parse_timer("m").is_err());
+    assert!(parse_timer("5x5m").is_err());
+}
+
+#[test]
+fn it_should_floor_whole_seconds_by_default() {
+    let time_format = TimeFormat {
+        hide_hundredths: true,
+        round_up: false,
+        hundredths_below: None,
+    };
+
+    assert_eq!("00:00:04", format_timer(4.99, time_format));
+    assert_eq!("00:00:05", format_timer(5.0, time_format));
+    assert_eq!("00:00:00", format_timer(0.01, time_format));
+}
+
+#[test]
+fn it_should_round_whole_seconds_up() {
+    let time_format = TimeFormat {
+        hide_hundredths: true,
+        round_up: true,
+        hundredths_below: None,
+    };
+
+    assert_eq!("00:00:05", format_timer(4.99, time_format));
+    assert_eq!("00:00:05", format_timer(5.0, time_format));
+    assert_eq!("00:00:01", format_timer(0.01, time_format));
+    assert_eq!("00:01:00", format_timer(59.5, time_format));
+    assert_eq!("00:00:00", format_timer(0.0, time_format));
+}
+
+#[test]
+fn it_should_find_the_label_for_each_round() {
+    let labels = vec!["Set 1".to_string(), "Set 2".to_string()];
+
+    assert_eq!(Some("Set 1"), label_for_round(&labels, 0));
+    assert_eq!(Some("Set 2"), label_for_round(&labels, 1));
+}
+
+#[test]
+fn it_should_wrap_the_labels_when_there_are_more_rounds() {
+    let labels = vec!["Work".to_string(), "Rest".to_string()];
+
+    assert_eq!(Some("Work"), label_for_round(&labels, 2));
+    assert_eq!(Some("Rest"), label_for_round(&labels, 5));
+    assert_eq!(None, label_for_round(&[], 0));
+}
+
+#[test]
+fn it_should_describe_a_frame_time() {
+    assert_eq!("60 fps 16.7 ms", describe_frame_time(1.0 / 60.0));
+    assert_eq!("4 fps 250.0 ms", describe_frame_time(0.25));
+}
+
+#[test]
+fn it_should_count_the_units_in_a_time() {
+    assert_eq!(20, count_units(10.0, 0.5, true));
+    assert_eq!(20, count_units(9.8, 0.5, true));
+    assert_eq!(19, count_units(9.8, 0.5, false));
+    assert_eq!(0, count_units(-1.0, 0.5, true));
+}
+
+#[test]
+fn it_should_scale_the_window_for_high_dpi_displays() {
+    assert_eq!(2.0, dpi_scale(192.0));
+    assert_eq!(1.0, dpi_scale(96.0));
+    assert_eq!(1.0, dpi_scale(72.0));
+    assert_eq!(1.0, dpi_scale(f32::NAN));
+}
+
+#[test]
+fn it_should_only_confirm_a_quit_soon_after_the_first_one() {
+    let asked_at = Instant::now();
+    let pending_quit = Some((asked_at, "Press Escape again to quit".to_string()));
+
+    assert!(quit_confirmed(&pending_quit, asked_at));
+    assert!(quit_confirmed(
+        &pending_quit,
+        asked_at + std::time::Duration::from_secs_f64(CONFIRM_QUIT_SECONDS / 2.0)
+    ));
+    assert!(!quit_confirmed(
+        &pending_quit,
+        asked_at + std::time::Duration::from_secs_f64(CONFIRM_QUIT_SECONDS)
+    ));
+    assert!(!quit_confirmed(&None, asked_at));
+}
+
+#[test]
+fn it_should_keep_the_laps_above_the_bottom_of_the_window() {
+    // With room to spare, they go right under the time
+    assert_eq!(600, laps_top(600, 1000, 3, 50));
+    // Otherwise they move up to end where the label starts
+    assert_eq!(770, laps_top(900, 920, 3, 50));
+    // And never off the top of a tiny window
+    assert_eq!(0, laps_top(20, 40, 3, 50));
+}
+
+#[test]
+fn it_should_mirror_a_rect_within_an_area() {
+    let area = Rect::new(100, 50, 400, 200);
+    let rect = Rect::new(120, 60, 40, 200);
+
+    assert_eq!(rect, mirror_rect(rect, area, false, false));
+    assert_eq!(
+        Rect::new(440, 60, 40, 200),
+        mirror_rect(rect, area, true, false)
+    );
+    assert_eq!(
+        Rect::new(120, 40, 40, 200),
+        mirror_rect(rect, area, false, true)
+    );
+}
+
+#[test]
+fn it_should_tile_an_image_across_the_window() {
+    assert_eq!(
+        vec![
+            Rect::new(0, 0, 300, 200),
+            Rect::new(300, 0, 300, 200),
+            Rect::new(0, 200, 300, 200),
+            Rect::new(300, 200, 300, 200),
+        ],
+        tile_rects(300, 200, 500, 300)
+    );
+}
+
+#[test]
+fn it_should_seek_to_where_the_progress_bar_was_clicked() {
+    let bar = Rect::new(0, 580, 800, 20);
+
+    assert_eq!(60.0, seek_time(0, bar, 60.0));
+    assert_eq!(30.0, seek_time(400, bar, 60.0));
+    assert_eq!(15.0, seek_time(600, bar, 60.0));
+    assert_eq!(0.0, seek_time(900, bar, 60.0));
+}
+
+#[test]
+fn it_should_repeat_the_notification_on_a_fixed_interval() {
+    assert!(!reminder_due(0, 29.0, Some(30.0)));
+    assert!(reminder_due(0, 30.0, Some(30.0)));
+    // Unlike `--nag`, it doesn't back off or stop
+    assert!(reminder_due(20, 30.0, Some(30.0)));
+
+    assert!(!reminder_due(1, 60.0, None));
+    assert!(reminder_due(1, 120.0, None));
+    assert!(!reminder_due(NAG_MAX_COUNT, 1e9, None));
+}
+
+#[test]
+fn it_should_double_the_wait_between_each_nag() {
+    assert_eq!(60.0, nag_interval(0));
+    assert_eq!(120.0, nag_interval(1));
+    assert_eq!(240.0, nag_interval(2));
+}
+
+#[test]
+fn it_should_stack_the_rows_down_the_area() {
+    let rows = stack_rows(&[1.0, 1.0, 2.0], Rect::new(0, 0, 300, 300));
+
+    assert_eq!(Rect::new(100, 0, 100, 100), rows[0]);
+    assert_eq!(Rect::new(100, 100, 100, 100), rows[1]);
+    assert_eq!(Rect::new(50, 200, 200, 100), rows[2]);
+}
+
+#[test]
+fn it_should_keep_the_row_of_an_empty_part() {
+    // The empty part at the end of "Done:" still takes up its share of the height
+    let rows = stack_rows(&[2.0, 0.0], Rect::new(0, 0, 200, 200));
+
+    assert_eq!(Rect::new(0, 0, 200, 100), rows[0]);
+    assert_eq!(Rect::new(100, 100, 0, 100), rows[1]);
+}
+
+#[test]
+fn it_should_shrink_a_row_that_is_too_wide() {
+    let rows = stack_rows(&[4.0], Rect::new(0, 0, 200, 100));
+
+    assert_eq!(Rect::new(0, 25, 200, 50), rows[0]);
+}
+
+#[test]
+fn it_should_invert_the_colors_for_the_final_seconds() {
+    assert!(!invert_colors(None, 5.0));
+    assert!(!invert_colors(Some(10.0), 10.5));
+    assert!(invert_colors(Some(10.0), 10.0));
+    assert!(invert_colors(Some(10.0), 0.01));
+    assert!(!invert_colors(Some(10.0), 0.0));
+}
+
+#[test]
+fn it_should_zoom_in_over_the_final_seconds() {
+    assert_eq!(1.0, zoom_final_scale(ZOOM_FINAL_SECONDS + 1.0));
+    assert_eq!(1.0, zoom_final_scale(ZOOM_FINAL_SECONDS));
+    assert_eq!(
+        1.0 + (ZOOM_FINAL_SCALE - 1.0) / 2.0,
+        zoom_final_scale(ZOOM_FINAL_SECONDS / 2.0)
+    );
+    assert_eq!(1.0, zoom_final_scale(0.0));
+}
+
+#[test]
+fn it_should_lay_the_columns_across_the_area() {
+    let columns = side_by_side(&[1.0, 2.0], Rect::new(0, 0, 400, 100));
+
+    assert_eq!(Rect::new(50, 0, 100, 100), columns[0]);
+    assert_eq!(Rect::new(200, 0, 200, 100), columns[1]);
+}
+
+#[test]
+fn it_should_shrink_a_column_that_is_too_tall() {
+    let columns = side_by_side(&[0.5, 4.0], Rect::new(0, 0, 200, 100));
+
+    assert_eq!(Rect::new(25, 0, 50, 100), columns[0]);
+    assert_eq!(Rect::new(100, 37, 100, 25), columns[1]);
+}
+
+#[test]
+fn it_should_keep_room_for_the_minimum_number_of_characters() {
+    assert_eq!(0.5, reserved_fraction(100, 20, 10));
+    assert_eq!(1.0, reserved_fraction(300, 20, 10));
+    assert_eq!(1.0, reserved_fraction(100, 20, 0));
+    assert!(reserved_fraction(100, 20, u32::MAX) < 0.001);
+}
+
+#[test]
+fn it_should_stop_once_the_maximum_runtime_is_reached() {
+    assert!(!runtime_exceeded(None, 86400.0));
+    assert!(!runtime_exceeded(Some(7200.0), 7199.9));
+    assert!(runtime_exceeded(Some(7200.0), 7200.0));
+}
+
+#[test]
+fn it_should_format_a_time_with_units() {
+    assert_eq!("1h30m", format_timer_units(5400.0));
+    assert_eq!("1h5s", format_timer_units(3605.0));
+    assert_eq!("1m30.5s", format_timer_units(90.5));
+    assert_eq!("0s", format_timer_units(0.0));
+}
+
+#[test]
+fn it_should_format_fractional_seconds_with_units() {
+    assert_eq!("1m30.1s", format_timer_units(90.1));
+    assert_eq!("1m0.25s", format_timer_units(60.25));
+    assert_eq!("10.5s", format_timer_units(10.5));
+    assert_eq!("1m10s", format_timer_units(70.0));
+    assert_eq!("0.01s", format_timer_units(0.01));
+    assert_eq!("1m", format_timer_units(59.999));
+}
+
+#[test]
+fn it_should_convert_between_the_duration_formats() {
+    let time_format = TimeFormat {
+        hide_hundredths: true,
+        round_up: false,
+        hundredths_below: None,
+    };
+
+    for time in [0.0, 45.0, 90.0, 5400.0, 3605.0, 86399.0] {
+        assert_eq!(Ok(time), parse_timer(&format_timer_units(time)));
+        assert_eq!(Ok(time), parse_timer(&format_timer(time, time_format)));
+    }
+}
+
+#[test]
+fn it_should_put_the_border_around_the_inside_of_the_window() {
+    let sides = border_rects(800, 600, 10);
+
+    assert_eq!(Rect::new(0, 0, 800, 10), sides[0]);
+    assert_eq!(Rect::new(0, 590, 800, 10), sides[1]);
+    assert_eq!(Rect::new(0, 10, 10, 580), sides[2]);
+    assert_eq!(Rect::new(790, 10, 10, 580), sides[3]);
+}
+
+#[test]
+fn it_should_count_down_to_the_next_boundary() {
+    // 10:20:00 to 11:00:00
+    assert_eq!(
+        2400.0,
+        until_next_boundary(10.0 * 3600.0 + 20.0 * 60.0, 3600.0)
+    );
+    // 10:20:00 to 10:30:00
+    assert_eq!(
+        600.0,
+        until_next_boundary(10.0 * 3600.0 + 20.0 * 60.0, 900.0)
+    );
+    // Right on 10:45:00, the next quarter hour is 11:00:00
+    assert_eq!(
+        900.0,
+        until_next_boundary(10.0 * 3600.0 + 45.0 * 60.0, 900.0)
+    );
+    // 23:59:30 to midnight
+    assert_eq!(30.0, until_next_boundary(86370.0, 3600.0));
+}
+
+#[test]
+fn it_should_only_show_the_hundredths_below_the_threshold() {
+    let time_format = TimeFormat {
+        hundredths_below: Some(60.0),
+        ..TimeFormat::default()
+    };
+
+    assert_eq!("03:00:00", format_timer(10800.0, time_format));
+    assert_eq!("00:01:00", format_timer(60.0, time_format));
+    assert_eq!("00:00:59.99", format_timer(59.99, time_format));
+    assert_eq!("00:00:05.25", format_timer(5.25, time_format));
+}
+
+#[test]
+fn it_should_center_the_window_on_the_display() {
+    let display = Rect::new(1920, 0, 2560, 1440);
+
+    assert_eq!((2800, 420), center_on(display, 800, 600));
+    assert_eq!((1920, 0), center_on(display, 3000, 1600));
+}
+
+#[test]
+fn it_should_cover_the_window_with_the_image() {
+    // A wide image is cut off at the sides
+    assert_eq!(
+        Rect::new(-100, 0, 1000, 600),
+        cover_rect(500, 300, 800, 600)
+    );
+    // A tall image is cut off at the top and bottom
+    assert_eq!(Rect::new(0, -100, 800, 800), cover_rect(100, 100, 800, 600));
+}
+
+#[test]
+fn it_should_round_the_timer_to_a_whole_second() {
+    assert_eq!(299.0, round_to_second(299.37));
+    assert_eq!(300.0, round_to_second(299.5));
+    assert_eq!(1.0, round_to_second(0.2));
+    assert_eq!(0.0, round_to_second(0.0));
+}
+
+#[test]
+fn it_should_find_the_beat_from_the_tempo() {
+    // Each beat at 120 bpm is half a second
+    assert_eq!((0, 0.0), beat_position(0.0, 120.0));
+    assert_eq!((2, 0.5), beat_position(1.25, 120.0));
+    assert_eq!((3, 0.0), beat_position(1.5, 120.0));
+
+    // At 90 bpm, 2 seconds is three beats
+    assert_eq!((3, 0.0), beat_position(2.0, 90.0));
+}
+
+#[test]
+fn it_should_format_the_percentage_complete() {
+    assert_eq!("0%", format_percent(progress_fraction(300.0, 300.0)));
+    assert_eq!("42%", format_percent(progress_fraction(174.0, 300.0)));
+    assert_eq!("100%", format_percent(progress_fraction(0.0, 300.0)));
+    assert_eq!("100%", format_percent(progress_fraction(0.0, 0.0)));
+}
+
+#[test]
+fn it_should_start_each_logo_off_in_its_own_direction() {
+    let velocities: Vec<Velocity> = (0..4).map(seed_velocity).collect();
+
+    assert_eq!(
+        Velocity {
+            x: VELOCITY_SPEED,
+            y: VELOCITY_SPEED
+        },
+        velocities[0]
+    );
+    for (i, velocity) in velocities.iter().enumerate() {
+        assert!(!velocities[..i].contains(velocity));
+    }
+}
+
+#[test]
+fn it_should_bounce_off_the_edges_of_the_window() {
+    let mut logo = TimerDisplay {
+        x: 1,
+        y: 50,
+        width: 20,
+        height: 10,
+        velocity: Some(Velocity { x: -2, y: 1 }),
+    };
+
+    // Reaching the left edge turns it around but keeps its speed
+    assert!(bounce(&mut logo, 100, 100, 0.0, 1.0));
+    assert_eq!((-1, 51), (logo.x, logo.y));
+    assert_eq!(Some(Velocity { x: 2, y: 1 }), logo.velocity);
+
+    // Out in the open it carries on as it was
+    assert!(!bounce(&mut logo, 100, 100, 0.0, 1.0));
+    assert_eq!((1, 52), (logo.x, logo.y));
+
+    // The bottom of the text, rather than of the display, is what hits the bottom edge
+    logo.y = 89;
+    assert!(!bounce(&mut logo, 100, 100, 0.0, 0.8));
+    logo.y = 91;
+    assert!(bounce(&mut logo, 100, 100, 0.0, 0.8));
+    assert_eq!(Some(Velocity { x: 2, y: -1 }), logo.velocity);
+}
+
+#[test]
+fn it_should_spread_the_other_logos_across_the_window() {
+    let logos = seed_logos(3, 1920, 1080, 480, 270);
+
+    assert_eq!(2, logos.len());
+    assert_eq!((480, 270), (logos[0].0.x, logos[0].0.y));
+    assert_eq!((960, 540), (logos[1].0.x, logos[1].0.y));
+
+    // Each one fits in the window, and starts off its own way in its own color
+    for (logo, _) in logos.iter() {
+        assert!(logo.x + logo.width as i32 <= 1920 && logo.y + logo.height as i32 <= 1080);
+    }
+    assert_ne!(logos[0].0.velocity, logos[1].0.velocity);
+    assert_ne!(logos[0].1, logos[1].1);
 }
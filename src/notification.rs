@@ -0,0 +1,187 @@
+#[cfg(all(unix, not(target_os = "macos")))]
+use dbus::{
+    arg::messageitem::{MessageItem, MessageItemArray},
+    ffidisp::Connection,
+    Message,
+};
+
+use std::time::Instant;
+
+pub const DEFAULT_TEMPLATE: &str = "Time's up!";
+pub const DEFAULT_START_TEMPLATE: &str = "Started {duration}";
+pub const MAX_RUNTIME_TEMPLATE: &str = "Stopped after running for the maximum of {duration}";
+const SYMBOL: &str = "\u{23f0}";
+
+#[derive(Debug, PartialEq)]
+pub enum Pending {
+    Waiting,
+    Due,
+    Cancelled,
+}
+
+/// A finish notification held back for a grace period, in case the timer is
+/// brought back to life straight after it finishes (e.g. by seeking it).
+pub struct PendingNotification {
+    pub summary: String,
+    pub body: String,
+    // The round of the timer that finished, so the next queued
+    // timer starting doesn't count as bringing this one back.
+    round: usize,
+    due: Instant,
+}
+
+impl PendingNotification {
+    pub fn new(summary: String, body: String, round: usize, due: Instant) -> PendingNotification {
+        PendingNotification {
+            summary,
+            body,
+            round,
+            due,
+        }
+    }
+
+    /// Whether this is the notification for the given round finishing
+    pub fn is_for(&self, round: usize) -> bool {
+        self.round == round
+    }
+
+    /// Whether the notification should be sent as of `now`, given the round
+    /// that's on and whether it's running. It's cancelled if the round that
+    /// finished is running again.
+    pub fn check(&self, now: Instant, round: usize, running: bool) -> Pending {
+        if round == self.round && running {
+            Pending::Cancelled
+        } else if now >= self.due {
+            Pending::Due
+        } else {
+            Pending::Waiting
+        }
+    }
+}
+
+/// Fill in the `{label}`, `{duration}`, and `{symbol}` tokens of a notification template
+pub fn fill_template(template: &str, label: &str, duration: &str) -> String {
+    template
+        .replace("{label}", label)
+        .replace("{duration}", duration)
+        .replace("{symbol}", SYMBOL)
+}
+
+/// For XDG desktops (besides macOS), we can use D-Bus to send a
+/// Desktop notification and let the user know that the timer
+/// has finished.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn send(summary: &str, body: &str) -> Result<(), String> {
+    let connection =
+        Connection::get_private(dbus::ffidisp::BusType::Session).map_err(|e| e.to_string())?;
+
+    let mut message = Message::new_method_call(
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications",
+        "Notify",
+    )?;
+
+    let program_name = "timer";
+    let id: u32 = 0;
+    let icon = "";
+    let actions = MessageItem::Array(MessageItemArray::new(vec![], "as".into()).unwrap());
+    let hints = MessageItem::Array(MessageItemArray::new(vec![], "a{sv}".into()).unwrap());
+    let timeout = 5000;
+
+    message.append_items(&[
+        program_name.into(),
+        id.into(),
+        icon.into(),
+        summary.into(),
+        body.into(),
+        actions,
+        hints,
+        timeout.into(),
+    ]);
+
+    connection
+        .send(message)
+        .map_err(|_| String::from("Could not send Desktop Notification Message"))?;
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn send(summary: &str, body: &str) -> Result<(), String> {
+    let bundle = mac_notification_sys::get_bundle_identifier_or_default("iterm");
+    mac_notification_sys::set_application(&bundle).unwrap();
+    let _ = mac_notification_sys::Notification::new()
+        .title(summary)
+        .message(body)
+        .sound("Ping")
+        .send()
+        .unwrap();
+
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn send(summary: &str, body: &str) -> Result<(), String> {
+    use winrt_notification::{Duration, Sound, Toast};
+
+    // Toasts aren't available on every version of Windows, and the flash
+    // still goes off without one, so a failed toast isn't fatal.
+    if let Err(e) = Toast::new(Toast::POWERSHELL_APP_ID)
+        .title(summary)
+        .text1(body)
+        .sound(Some(Sound::Default))
+        .duration(Duration::Short)
+        .show()
+    {
+        crate::warn(format!("could not show the notification: {}", e));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn send(_summary: &str, _body: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[test]
+fn it_should_fill_in_the_template_tokens() {
+    assert_eq!(
+        "\u{23f0} Tea done after 00:05:00",
+        fill_template("{symbol} {label} done after {duration}", "Tea", "00:05:00")
+    );
+}
+
+#[test]
+fn it_should_leave_a_template_without_tokens_alone() {
+    assert_eq!(
+        "Time's up!",
+        fill_template(DEFAULT_TEMPLATE, "Tea", "00:05:00")
+    );
+}
+
+#[test]
+fn it_should_hold_a_pending_notification_until_the_grace_period_is_over() {
+    let finished_at = Instant::now();
+    let due = finished_at + std::time::Duration::from_secs(2);
+    let pending = PendingNotification::new("Timer".into(), "Time's up!".into(), 0, due);
+
+    assert_eq!(Pending::Waiting, pending.check(finished_at, 0, false));
+    assert_eq!(Pending::Due, pending.check(due, 0, false));
+}
+
+#[test]
+fn it_should_cancel_a_pending_notification_if_the_timer_is_revived() {
+    let finished_at = Instant::now();
+    let due = finished_at + std::time::Duration::from_secs(2);
+    let pending = PendingNotification::new("Timer".into(), "Time's up!".into(), 0, due);
+
+    assert_eq!(Pending::Cancelled, pending.check(finished_at, 0, true));
+    assert_eq!(Pending::Cancelled, pending.check(due, 0, true));
+
+    // The next queued timer starting isn't the finished one coming back
+    assert_eq!(Pending::Waiting, pending.check(finished_at, 1, true));
+    assert!(pending.is_for(0));
+    assert!(!pending.is_for(1));
+}
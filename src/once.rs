@@ -0,0 +1,263 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Held by the timer started with `--once` for as long as it runs. Dropping it
+/// removes the lock file, so that the next timer with the same name can start.
+pub struct InstanceLock {
+    path: PathBuf,
+    // Windows has no cheap way to check on a process, so instead the lock is kept open
+    // in a way that stops anyone else deleting it until this timer is gone
+    #[cfg(windows)]
+    handle: Option<std::fs::File>,
+}
+
+/// Check that the name can safely go in the lock file's name, which only
+/// letters, numbers, dashes, and underscores can
+pub fn check_name(name: &str) -> Result<(), String> {
+    match !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        true => Ok(()),
+        _ => Err(format!(
+            "Invalid name: \"{}\" (use letters, numbers, dashes, and underscores)",
+            name
+        )),
+    }
+}
+
+impl InstanceLock {
+    /// Take the lock for `name`, unless another timer that's still running has it.
+    /// A lock left behind by a timer that crashed is taken over.
+    pub fn acquire(name: &str) -> Result<InstanceLock, String> {
+        InstanceLock::acquire_in(&crate::dirs::runtime_dir(), name)
+    }
+
+    fn acquire_in(dir: &Path, name: &str) -> Result<InstanceLock, String> {
+        check_name(name)?;
+
+        let path = lock_path(dir, name);
+
+        // The lock is written out in full under a name of its own and then linked into
+        // place, so another timer starting at the same moment never sees it half written
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+
+        std::fs::write(&temporary, std::process::id().to_string()).map_err(|e| {
+            format!(
+                "Could not create the lock file \"{}\": {}",
+                temporary.display(),
+                e
+            )
+        })?;
+        let linked = link_lock(name, &temporary, &path);
+        let _ = std::fs::remove_file(&temporary);
+        linked?;
+
+        Ok(InstanceLock {
+            #[cfg(windows)]
+            handle: open_held(&path),
+            path,
+        })
+    }
+}
+
+/// Where the lock for `name` goes in `dir`
+fn lock_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("timer-{}.lock", name))
+}
+
+/// Link the written out lock into place, taking over a stale one if it's in the way
+fn link_lock(name: &str, temporary: &Path, path: &Path) -> Result<(), String> {
+    for _ in 0..2 {
+        match std::fs::hard_link(temporary, path) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                if still_held(path) {
+                    let pid =
+                        read_pid(path).map_or(String::new(), |pid| format!(" (process {})", pid));
+
+                    return Err(format!(
+                        "A timer named \"{}\" is already running{}. If it isn't, remove \"{}\".",
+                        name,
+                        pid,
+                        path.display()
+                    ));
+                }
+
+                set_aside_stale(path);
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Could not create the lock file \"{}\": {}",
+                    path.display(),
+                    e
+                ))
+            }
+        }
+    }
+
+    Err(format!(
+        "Could not take over the lock file \"{}\"",
+        path.display()
+    ))
+}
+
+/// Move a stale lock out of the way. Renaming it is atomic, so only one timer gets
+/// hold of it, and if another timer took the name in the meantime, it's the new lock
+/// that was moved, so that one's put back rather than deleted.
+fn set_aside_stale(path: &Path) {
+    let mut aside = path.as_os_str().to_owned();
+    aside.push(format!(".{}.stale", std::process::id()));
+    let aside = PathBuf::from(aside);
+
+    if std::fs::rename(path, &aside).is_err() {
+        return;
+    }
+
+    if still_held(&aside) {
+        let _ = std::fs::hard_link(&aside, path);
+    }
+    let _ = std::fs::remove_file(&aside);
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        #[cfg(windows)]
+        drop(self.handle.take());
+
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The process that took the lock, as written in the lock file
+fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+}
+
+/// Whether the timer that took the lock is still running. A lock that doesn't
+/// say which process took it is left alone, since there's no telling.
+#[cfg(unix)]
+fn still_held(path: &Path) -> bool {
+    read_pid(path).is_none_or(is_running)
+}
+
+/// Whether the process is still around. Signal 0 only checks that it's there, and
+/// a process that belongs to someone else can't be signalled but is still running.
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    // Anything from 0 down stands for a group of processes rather than just one
+    let pid = match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => pid,
+        _ => return false,
+    };
+
+    // SAFETY: sending signal 0 has no effect on the process
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// The running timer holds its lock open so that it can't be deleted,
+/// so a lock that can be deleted was left behind by one that's gone
+#[cfg(windows)]
+fn still_held(path: &Path) -> bool {
+    std::fs::remove_file(path).is_err()
+}
+
+/// Open the lock so that it can still be read, but not deleted while it's open
+#[cfg(windows)]
+fn open_held(path: &Path) -> Option<std::fs::File> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const FILE_SHARE_READ: u32 = 0x1;
+
+    std::fs::OpenOptions::new()
+        .read(true)
+        .share_mode(FILE_SHARE_READ)
+        .open(path)
+        .map_err(|e| crate::warn(format!("could not hold the lock file open: {}", e)))
+        .ok()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn still_held(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(test)]
+fn test_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("timer-once-{}-{}", test, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn it_should_refuse_a_second_timer_with_the_same_name() {
+    let dir = test_dir("second");
+
+    let lock = InstanceLock::acquire_in(&dir, "focus").unwrap();
+    assert!(InstanceLock::acquire_in(&dir, "focus").is_err());
+
+    // Once the first timer is done, the name is free again
+    drop(lock);
+    let lock = InstanceLock::acquire_in(&dir, "focus");
+    assert!(lock.is_ok());
+
+    drop(lock);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn it_should_take_over_the_lock_of_a_timer_that_is_gone() {
+    let dir = test_dir("stale");
+
+    // No process can have the largest ID, since it's past the most the system hands out
+    std::fs::write(lock_path(&dir, "focus"), libc::pid_t::MAX.to_string()).unwrap();
+    let lock = InstanceLock::acquire_in(&dir, "focus");
+    assert!(lock.is_ok());
+    assert_eq!(
+        Some(std::process::id()),
+        read_pid(&lock_path(&dir, "focus"))
+    );
+
+    drop(lock);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn it_should_put_back_a_lock_taken_while_setting_aside_a_stale_one() {
+    let dir = test_dir("put-back");
+    let path = lock_path(&dir, "focus");
+
+    // By the time it's set aside, the stale lock has been replaced by a running timer's
+    std::fs::write(&path, std::process::id().to_string()).unwrap();
+    set_aside_stale(&path);
+    assert_eq!(Some(std::process::id()), read_pid(&path));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn it_should_leave_a_lock_it_cannot_read_alone() {
+    let dir = test_dir("unreadable");
+
+    std::fs::write(lock_path(&dir, "focus"), "").unwrap();
+    assert!(InstanceLock::acquire_in(&dir, "focus").is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn it_should_reject_a_name_that_is_not_safe_for_a_file_name() {
+    assert!(check_name("focus_25-min").is_ok());
+    assert!(check_name("").is_err());
+    assert!(check_name("a/b").is_err());
+    assert!(check_name("../timer").is_err());
+}
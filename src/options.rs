@@ -0,0 +1,1081 @@
+use crate::chess;
+use crate::color;
+use crate::cues::{self, Cue};
+use crate::geometry::{self, Edge};
+use crate::keys::{self, Action};
+use crate::notification;
+use crate::once;
+use crate::speech;
+use crate::state::TimerMode;
+use crate::stopwatches::MAX_STOPWATCHES;
+use crate::theme::{self, THEMES};
+use crate::{parse_numerals, parse_timer, DisplayMode, Numerals, TimeFormat, MAX_DVD_LOGOS};
+
+use sdl2::{keyboard::Keycode, pixels::Color};
+
+use std::collections::HashMap;
+
+const SHADOW_OFFSET: f32 = 0.03;
+const TEXT_PADDING: f32 = 0.1;
+const TEXT_SIZE: f32 = 0.8;
+
+pub const VIDEO_DRIVERS: &[&str] = &[
+    "x11",
+    "wayland",
+    "kmsdrm",
+    "cocoa",
+    "windows",
+    "offscreen",
+    "dummy",
+];
+
+pub const USAGE: &str = r#"
+Usage: timer [options] [timer]
+
+Start a timer that counts down to the time specified. Press the <space> key to pause it,
+and the <L> key to record a lap.
+
+Timer:
+    hh:mm:ss        The format of the countdown timer
+                    (e.g. "10" is ten seconds, "1:30" is a minute and thirty seconds, etc.)
+    1h30m15s        The timer can also be given with units, using any of "h", "m", and "s"
+                    (e.g. "5m" is five minutes, "1h30m" is an hour and a half, etc.)
+
+Options:
+    --stopwatch     Start a timer that increments rather than counts down to a specified time
+                    This option does not require the "timer" argument.
+    --dvd           Have the timer bounce around the screen like a DVD screen saver
+    --restart-on-focus
+                    Reset the countdown to its initial value whenever the window regains focus
+    --start-paused  Open with the timer paused until it's started with the pause key. With
+                    --restart-on-focus, it waits to be started again after each reset.
+    --color <color> The color of the timer text (e.g. "ff8000", "f80", or "orange")
+    --background <color>
+                    The color of the window background
+    --dvd-elapsed   Show a small elapsed time counter in the corner while in DVD mode
+    --flash-screen  Flash the whole window red for a couple of seconds when the timer finishes
+    --message <text>
+                    Show this message in place of the time once the timer finishes
+    --static-finish Show the finished timer without blinking or flashing
+    --min-font-size <pixels>
+                    Never draw the text shorter than this, even if it has to be clipped
+    --alert-flash <hz>
+                    Flash the whole window this many times a second once the timer finishes,
+                    until a key is pressed or the window is clicked.
+                    WARNING: fast flashing can trigger seizures in people with photosensitive
+                    epilepsy. Rates of 3 Hz and above are the most dangerous.
+    --alert-color <color>
+                    The color of the --alert-flash (defaults to white). Give the color an
+                    alpha (e.g. "ffffff80") to soften the flash.
+    --video-driver <driver>
+                    Force SDL to use a particular video driver
+                    (one of "x11", "wayland", "kmsdrm", "cocoa", "windows", "offscreen", or "dummy")
+    --headless      Run the timer in the terminal with a progress bar instead of opening a window
+    --verbose       Print extra details about the timer's setup
+    --debug-layout  Outline where the timer text is drawn, along with its center and baseline
+    --hide-cursor   Hide the mouse cursor when it hasn't moved over the window for a moment
+    --countdown-beeps <seconds>
+                    Beep on each of the last few seconds and play a longer beep at zero
+    --stdin         Read timers from stdin, one per line, and run them one after another
+    --shadow        Draw a drop shadow behind the timer text
+    --shadow-color <color>
+                    The color of the drop shadow (defaults to a half transparent black, "00000080")
+    --shadow-offset <fraction>
+                    How far the shadow sits from the text, as a fraction of the text's height
+                    from 0 to 1 (defaults to 0.03)
+    --hide-hundredths
+                    Only show the time down to the second
+    --round-up      Round the remaining time up to the next whole second when the hundredths
+                    are hidden, so "00:00:01" stays up right until the timer finishes
+    --auto-quit-after <duration>
+                    Quit once the finished timer has been dismissed (with a key press or a
+                    click) and left alone for this long (e.g. "1m")
+    --repeat <count>
+                    Run the timer this many times in a row
+    --labels <labels>
+                    A comma separated list of labels to show for each round of the timer
+                    (e.g. "Set 1,Set 2,Set 3"). The labels start over if there are more rounds.
+    --notify-template <template>
+                    The body of the notification sent when the timer finishes. The tokens
+                    {label}, {duration}, and {symbol} are filled in with the timer's label,
+                    its starting time, and an alarm clock (e.g. "{symbol} {label} done")
+    --speak-at <times>
+                    Say how much time is left out loud as the timer passes each of these
+                    comma separated times (e.g. "5m,1m")
+    --speak-command <command>
+                    The text to speech command used to speak, which is given the text to say
+                    as its last argument (defaults to "spd-say", or "say" on macOS)
+    --smooth-resize Ease the text into its new size when the window is resized
+    --inhibit-sleep Keep the system awake while the timer is running (not while paused)
+    --keys <bindings>
+                    Remap the keyboard shortcuts using a comma separated list of action=Key
+                    (e.g. "pause=P,quit=Q"). The actions are "pause", "quit", "lap", "theme",
+                    and "again", which queues up another run of the countdown (N by default).
+    --numerals <system>
+                    Render the digits using another numeral system
+                    (one of "ascii", "arabic", or "devanagari")
+    --remember-geometry
+                    Reopen the window where it was, and at the size it was, when it last closed
+    --mirror        Flip the timer text horizontally, for reading it in a mirror
+    --mirror-vertical
+                    Flip the timer text upside down
+    --show-fps      Show the frame rate and how long each frame takes in the top right corner
+    --unit-seconds <seconds>
+                    Count the time in units of this many seconds instead of hours, minutes,
+                    and seconds (e.g. "0.5" counts half second beats)
+    --unit-label <label>
+                    What to call the units counted with --unit-seconds (defaults to "units")
+    --preroll <seconds>
+                    Count down this many seconds, one big number at a time, before the timer
+                    itself starts
+    --preroll-beeps Beep on each number of the --preroll, with a longer beep as the timer starts
+    --list-fonts    Print the paths of the fonts installed on the system and exit
+    --flip-clock    Roll each digit into place when it changes, like a mechanical clock.
+                    This looks best with --hide-hundredths.
+    --minimized     Start with the window minimized. The window flashes when the timer finishes.
+    --scale-with-monitor-dpi
+                    Size the window for the display's DPI, so the clock is about the same
+                    physical size on any display
+    --laps-out <file>
+                    Save the recorded laps to this file when the timer quits, instead of
+                    printing them
+    --confirm-quit  Only quit when asked to twice in a row, so one stray key press doesn't
+                    end a long timer
+    --lcd           Draw the time with the segments of a seven segment display
+    --pause-on-blur Pause the timer while the window doesn't have focus
+    --write-to <file>
+                    Keep this file up to date with the time on the clock, rewriting it once a
+                    second (e.g. for a text source in OBS). This works with --headless too.
+    --warn-at <times>
+                    Send a notification as the timer passes each of these comma separated
+                    times (e.g. "5m,1m")
+    --warn-flash    Flash the window for each --warn-at warning too
+    --no-warn-flash Only send a notification for each --warn-at warning (the default)
+    --padding <fraction>
+                    The margin around the timer, as a fraction or a percentage of the window
+                    (defaults to 0.1, or 10%)
+    --text-size <fraction>
+                    How much of the window the timer fills, as a fraction or a percentage
+                    (defaults to 0.8, or 80%). The text size and the padding on each side
+                    can add up to at most the whole window.
+    --dvd-trail     Leave a fading trail behind the timer as it bounces around in DVD mode
+    --announce-start
+                    Send a notification when the timer starts as well as when it finishes
+    --start-template <template>
+                    The body of the --announce-start notification, which takes the same
+                    tokens as --notify-template (defaults to "Started {duration}")
+    --progress-bar  Show a bar along the bottom of the window that fills up as the countdown
+                    runs. Click on the bar to jump the countdown to that point.
+    --display <index>
+                    Open the window on this display, counting from 0 for the primary display
+    --fullscreen    Fill the whole display with the timer
+    --alarm-fade-in <duration>
+                    Play an alarm when the timer finishes that starts quietly and gets louder
+                    over this long (e.g. "2s")
+    --theme <theme> Use the text and background colors of a built-in theme (one of "dark",
+                    "light", "solarized-dark", "solarized-light", "amber", or "green").
+                    Press <T> to cycle through the themes while the timer runs.
+    --grace <duration>
+                    Wait this long after the timer finishes before sending the notification,
+                    and skip it if the timer is brought back in the meantime (e.g. "2s")
+    --tile <edge>   Fit the window along an edge of the display as a strip, or into a corner
+                    (one of "top", "bottom", "left", "right", "top-left", "top-right",
+                    "bottom-left", or "bottom-right")
+    --borderless    Open the window without a title bar or border
+    --always-on-top Keep the window above the other windows
+    --audio-device <name>
+                    Play the beeps and alarms on this audio device instead of the default one
+    --list-audio-devices
+                    Print the names of the audio devices and exit
+    --nag           Keep sending the notification until the finished timer is dismissed,
+                    after 1 minute, then 2, then 4, and so on, up to 6 reminders
+    --vertical      Stack the hours, minutes, and seconds on top of each other, for a tall
+                    and narrow window
+    --test-notification
+                    Send the finish notification and play the alarm straight away, then exit
+                    without running the timer
+    --cue <time>:<text>
+                    Show some text across the top of the window for a few seconds as the
+                    countdown passes the time (e.g. --cue 2m:"Switch sides"). Give it more
+                    than once for more cues.
+    --invert-final <duration>
+                    Swap the text and background colors for the last stretch of the countdown
+                    (e.g. "10s")
+    --quiet         Don't print warnings about things that didn't work but weren't worth
+                    quitting over, like a notification that couldn't be sent. Errors that
+                    stop the timer are still printed.
+    --zoom-final    Slowly zoom in on the time over the last couple of seconds of the countdown
+    --show-both     Show the elapsed time and the time remaining side by side, or one above
+                    the other with --vertical. This doesn't apply to the stopwatch.
+    --min-width-chars <count>
+                    Keep room for at least this many digits across the window, so the time
+                    keeps the same size when it gets shorter (e.g. when the hours drop off)
+    --rpc           Take commands from stdin as lines of JSON, like {"cmd":"set","seconds":300}.
+                    The commands are "set", "pause", "resume", "restart", and "quit". Events
+                    like {"event":"finished"} and errors are written to stdout the same way.
+                    This can't be used with --stdin, and doesn't apply to --headless.
+    --dump-metrics  Print the font's metrics that the DVD bounce is worked out from, then exit
+    --lock          Ignore the quit key and closing the window until the countdown finishes.
+                    To get out early anyway, kill the process (e.g. "pkill timer" in a
+                    terminal). This doesn't apply to the stopwatch.
+    --resume        When no timer is given and the last countdown was quit before it finished,
+                    pick it back up with the time it had left, staying paused if it was paused.
+                    A timer that is given is started as usual.
+    --hollow        Draw only the outline of the text, leaving the inside of each letter clear
+    --outline <color>
+                    The color of the outline with --hollow (defaults to the text color)
+    --max-runtime <duration>
+                    Quit once the timer has been open this long, sending a notification to say
+                    so, no matter what the clock shows (e.g. "2h" so a forgotten stopwatch
+                    doesn't run forever)
+    --convert <duration>
+                    Print the duration in seconds, with units, and as hh:mm:ss, then exit
+                    (e.g. "01:30:00" prints "5400s", "1h30m", and "01:30:00")
+    --border-flash  Pulse a thick border around the edge of the window once the timer finishes,
+                    until it's dismissed
+    --border-color <color>
+                    The color of the --border-flash border (defaults to red)
+    --border-width <fraction>
+                    How thick the --border-flash border is, as a fraction or a percentage of the
+                    window (defaults to 0.03, or 3%)
+    --stopwatches <count>
+                    Show up to 4 stopwatches side by side, like one for each player, that each
+                    start and stop with their number key (1 to 4). Any number of them can run at
+                    once. With --vertical they're stacked instead.
+    --chess <time>[+<increment>]
+                    Show a clock for each side of a two player game, where space ends a move
+                    and hands the clock over to the other side. The increment is added on to
+                    a side's time after each of its moves (e.g. "5m+3s"). A side that runs out
+                    of time flashes red.
+    --clipboard     Take the timer from the clipboard, like a duration copied from somewhere else
+    --opacity <fraction>
+                    How see through the whole window is, from 0 (invisible) to 1 (solid), where
+                    the desktop supports it (e.g. "0.7" or "70%")
+    --next-hour     Count down to the top of the next hour on the clock
+    --next <interval>
+                    Count down to the next time the clock reaches a multiple of the interval
+                    (e.g. "15m" to count down to the next quarter hour)
+    --history <path>
+                    Add a line to this CSV file for each timer that finishes, and for a stopwatch
+                    when it's stopped, with when it started, how many seconds it ran, its label,
+                    and whether it was a countdown or a stopwatch
+    --auto-precision
+                    Only show the hundredths once there's less than a minute on the clock
+    --auto-precision-below <duration>
+                    Only show the hundredths once the time is under this (e.g. "5m")
+    --background-image <path>
+                    Draw a PNG or JPEG image behind the timer instead of the background color,
+                    scaled to cover the window
+    --background-tile
+                    Repeat the --background-image across the window at its own size instead
+                    of scaling it
+    --mirror-file <file>
+                    Show the time from a file kept up to date by another timer's --write-to
+                    instead of running a timer here, like a big clock on a second monitor.
+                    The time is grayed out once it stops changing or the file goes away.
+    --round-start   Round the timer to the nearest whole second before it starts, so that
+                    a time like the one from --next-hour ticks over on whole seconds
+    --metronome <bpm>
+                    Flash a beat at the top of the window at this many beats per minute
+                    while the timer is counting (e.g. "120")
+    --metronome-click
+                    Click on each beat of the metronome as well
+    --repeat-notification <interval>
+                    Send the notification again this often (e.g. "30s") until the finished
+                    timer is dismissed with a key or a click, or the window is focused
+    --once <name>   Refuse to start if a timer with this name is already running, for
+                    when the timer is bound to a key that might be pressed twice
+    --show-percent  Show how much of the countdown is done as a percentage above the time
+    --speak-countdown <seconds>
+                    Count the last few seconds down out loud and say "time" at zero, using
+                    the --speak-command
+    --dvd-multi <count>
+                    Bounce this many copies of the timer around the screen, each going its
+                    own way and changing color whenever it hits an edge (implies --dvd)
+
+Exit status:
+    0               The timer ran to completion, or a stopwatch was stopped
+    1               There was an error
+    2               The timer was quit before it finished
+"#;
+
+pub struct Options {
+    pub timer: Option<f64>,
+    pub display_mode: DisplayMode,
+    pub timer_mode: TimerMode,
+    pub restart_on_focus: bool,
+    pub start_paused: bool,
+    pub text_color: Color,
+    pub background_color: Color,
+    pub dvd_elapsed: bool,
+    pub flash_screen: bool,
+    pub message: Option<String>,
+    pub static_finish: bool,
+    pub min_font_size: Option<u32>,
+    pub alert_flash: Option<f64>,
+    pub alert_color: Color,
+    pub video_driver: Option<String>,
+    pub headless: bool,
+    pub verbose: bool,
+    pub debug_layout: bool,
+    pub hide_cursor: bool,
+    pub countdown_beeps: Option<u32>,
+    pub read_stdin: bool,
+    pub shadow: bool,
+    pub shadow_color: Color,
+    pub shadow_offset: f32,
+    pub time_format: TimeFormat,
+    pub auto_quit_after: Option<f64>,
+    pub repeat: u32,
+    pub labels: Vec<String>,
+    pub notify_template: String,
+    pub speak_at: Vec<f64>,
+    pub speak_command: String,
+    pub smooth_resize: bool,
+    pub inhibit_sleep: bool,
+    pub key_bindings: HashMap<Keycode, Action>,
+    pub numerals: Numerals,
+    pub remember_geometry: bool,
+    pub mirror: bool,
+    pub mirror_vertical: bool,
+    pub show_fps: bool,
+    pub unit_seconds: Option<f64>,
+    pub unit_label: String,
+    pub preroll: u32,
+    pub preroll_beeps: bool,
+    pub list_fonts: bool,
+    pub flip_clock: bool,
+    pub minimized: bool,
+    pub scale_with_monitor_dpi: bool,
+    pub laps_out: Option<String>,
+    pub confirm_quit: bool,
+    pub lcd: bool,
+    pub pause_on_blur: bool,
+    pub write_to: Option<String>,
+    pub warn_at: Vec<f64>,
+    pub warn_flash: bool,
+    pub padding: f32,
+    pub text_size: f32,
+    pub dvd_trail: bool,
+    pub announce_start: bool,
+    pub start_template: String,
+    pub progress_bar: bool,
+    pub display: Option<i32>,
+    pub fullscreen: bool,
+    pub alarm_fade_in: Option<f64>,
+    // The built-in theme the colors came from, if they came from one
+    pub theme: Option<usize>,
+    pub grace: Option<f64>,
+    pub tile: Option<Edge>,
+    pub borderless: bool,
+    pub always_on_top: bool,
+    pub audio_device: Option<String>,
+    pub list_audio_devices: bool,
+    pub nag: bool,
+    pub vertical: bool,
+    pub test_notification: bool,
+    pub cues: Vec<Cue>,
+    pub invert_final: Option<f64>,
+    pub quiet: bool,
+    pub zoom_final: bool,
+    pub show_both: bool,
+    pub min_width_chars: Option<u32>,
+    pub rpc: bool,
+    pub dump_metrics: bool,
+    pub lock: bool,
+    pub resume: bool,
+    pub hollow: bool,
+    pub outline_color: Option<Color>,
+    pub max_runtime: Option<f64>,
+    pub convert: Option<f64>,
+    pub border_flash: bool,
+    pub border_color: Color,
+    pub border_width: f32,
+    pub stopwatches: Option<usize>,
+    pub chess: Option<(f64, f64)>,
+    pub clipboard: bool,
+    pub opacity: Option<f32>,
+    pub next: Option<f64>,
+    pub history: Option<String>,
+    pub background_image: Option<String>,
+    pub background_tile: bool,
+    pub mirror_file: Option<String>,
+    pub round_start: bool,
+    pub metronome: Option<f64>,
+    pub metronome_click: bool,
+    pub repeat_notification: Option<f64>,
+    pub once: Option<String>,
+    pub show_percent: bool,
+    pub speak_countdown: Option<u32>,
+    pub dvd_logos: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            timer: None,
+            display_mode: DisplayMode::Default,
+            timer_mode: TimerMode::Default,
+            restart_on_focus: false,
+            start_paused: false,
+            text_color: Color::RGB(255, 255, 255),
+            background_color: Color::RGB(0, 0, 0),
+            dvd_elapsed: false,
+            flash_screen: false,
+            message: None,
+            static_finish: false,
+            min_font_size: None,
+            alert_flash: None,
+            alert_color: Color::RGB(255, 255, 255),
+            video_driver: None,
+            headless: false,
+            verbose: false,
+            debug_layout: false,
+            hide_cursor: false,
+            countdown_beeps: None,
+            read_stdin: false,
+            shadow: false,
+            shadow_color: Color::RGBA(0, 0, 0, 128),
+            shadow_offset: SHADOW_OFFSET,
+            time_format: TimeFormat::default(),
+            auto_quit_after: None,
+            repeat: 1,
+            labels: Vec::new(),
+            notify_template: notification::DEFAULT_TEMPLATE.to_string(),
+            speak_at: Vec::new(),
+            speak_command: speech::DEFAULT_COMMAND.to_string(),
+            smooth_resize: false,
+            inhibit_sleep: false,
+            key_bindings: keys::default_key_bindings(),
+            numerals: Numerals::Ascii,
+            remember_geometry: false,
+            mirror: false,
+            mirror_vertical: false,
+            show_fps: false,
+            unit_seconds: None,
+            unit_label: "units".to_string(),
+            preroll: 0,
+            preroll_beeps: false,
+            list_fonts: false,
+            flip_clock: false,
+            minimized: false,
+            scale_with_monitor_dpi: false,
+            laps_out: None,
+            confirm_quit: false,
+            lcd: false,
+            pause_on_blur: false,
+            write_to: None,
+            warn_at: Vec::new(),
+            warn_flash: false,
+            padding: TEXT_PADDING,
+            text_size: TEXT_SIZE,
+            dvd_trail: false,
+            announce_start: false,
+            start_template: notification::DEFAULT_START_TEMPLATE.to_string(),
+            progress_bar: false,
+            display: None,
+            fullscreen: false,
+            alarm_fade_in: None,
+            theme: None,
+            grace: None,
+            tile: None,
+            borderless: false,
+            always_on_top: false,
+            audio_device: None,
+            list_audio_devices: false,
+            nag: false,
+            vertical: false,
+            test_notification: false,
+            cues: Vec::new(),
+            invert_final: None,
+            quiet: false,
+            zoom_final: false,
+            show_both: false,
+            min_width_chars: None,
+            rpc: false,
+            dump_metrics: false,
+            lock: false,
+            resume: false,
+            hollow: false,
+            outline_color: None,
+            max_runtime: None,
+            convert: None,
+            border_flash: false,
+            border_color: Color::RGB(255, 0, 0),
+            border_width: 0.03,
+            stopwatches: None,
+            chess: None,
+            clipboard: false,
+            opacity: None,
+            next: None,
+            history: None,
+            background_image: None,
+            background_tile: false,
+            mirror_file: None,
+            round_start: false,
+            metronome: None,
+            metronome_click: false,
+            repeat_notification: None,
+            once: None,
+            show_percent: false,
+            speak_countdown: None,
+            dvd_logos: 1,
+        }
+    }
+}
+
+/// Parse the command line arguments, not including the executable name
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut args = args.into_iter();
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stopwatch" => options.timer_mode = TimerMode::Stopwatch,
+            "--dvd" => options.display_mode = DisplayMode::DVD,
+            "--restart-on-focus" => options.restart_on_focus = true,
+            "--start-paused" => options.start_paused = true,
+            "--color" => {
+                let value = args.next().ok_or("Missing value for --color")?;
+                options.text_color = color::parse_color(&value)?;
+                options.theme = None;
+            }
+            "--background" => {
+                let value = args.next().ok_or("Missing value for --background")?;
+                options.background_color = color::parse_color(&value)?;
+                options.theme = None;
+            }
+            "--dvd-elapsed" => options.dvd_elapsed = true,
+            "--flash-screen" => options.flash_screen = true,
+            "--message" => {
+                let value = args.next().ok_or("Missing value for --message")?;
+                if value.is_empty() {
+                    return Err("Invalid message: the message can't be empty".to_string());
+                }
+
+                options.message = Some(value);
+            }
+            "--static-finish" => options.static_finish = true,
+            "--min-font-size" => {
+                let value = args.next().ok_or("Missing value for --min-font-size")?;
+                options.min_font_size = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid min font size: \"{}\"", value))?,
+                );
+            }
+            "--alert-flash" => {
+                let value = args.next().ok_or("Missing value for --alert-flash")?;
+                let hz = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid alert flash: \"{}\"", value))?;
+                if !hz.is_finite() || hz <= 0.0 {
+                    return Err("Invalid alert flash: the rate must be above 0 Hz".to_string());
+                }
+
+                options.alert_flash = Some(hz);
+            }
+            "--alert-color" => {
+                let value = args.next().ok_or("Missing value for --alert-color")?;
+                options.alert_color = color::parse_color(&value)?;
+            }
+            "--video-driver" => {
+                let value = args.next().ok_or("Missing value for --video-driver")?;
+                if !VIDEO_DRIVERS.contains(&value.as_str()) {
+                    return Err(format!("Invalid video driver: \"{}\"", value));
+                }
+
+                options.video_driver = Some(value);
+            }
+            "--headless" => options.headless = true,
+            "--verbose" => options.verbose = true,
+            "--debug-layout" => options.debug_layout = true,
+            "--hide-cursor" => options.hide_cursor = true,
+            "--countdown-beeps" => {
+                let value = args.next().ok_or("Missing value for --countdown-beeps")?;
+                options.countdown_beeps = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid countdown beeps: \"{}\"", value))?,
+                );
+            }
+            "--stdin" => options.read_stdin = true,
+            "--shadow" => options.shadow = true,
+            "--shadow-color" => {
+                let value = args.next().ok_or("Missing value for --shadow-color")?;
+                options.shadow_color = color::parse_color(&value)?;
+            }
+            "--shadow-offset" => {
+                let value = args.next().ok_or("Missing value for --shadow-offset")?;
+                options.shadow_offset = parse_fraction(&value)
+                    .ok_or(format!("Invalid shadow offset: \"{}\"", value))?;
+            }
+            "--hide-hundredths" => options.time_format.hide_hundredths = true,
+            "--round-up" => options.time_format.round_up = true,
+            "--auto-quit-after" => {
+                let value = args.next().ok_or("Missing value for --auto-quit-after")?;
+                options.auto_quit_after = Some(parse_timer(&value)?);
+            }
+            "--repeat" => {
+                let value = args.next().ok_or("Missing value for --repeat")?;
+                options.repeat = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|repeat| *repeat > 0)
+                    .ok_or(format!("Invalid repeat: \"{}\"", value))?;
+            }
+            "--labels" => {
+                let value = args.next().ok_or("Missing value for --labels")?;
+                options.labels = value
+                    .split(',')
+                    .map(|label| label.trim().to_string())
+                    .collect();
+            }
+            "--notify-template" => {
+                options.notify_template =
+                    args.next().ok_or("Missing value for --notify-template")?;
+            }
+            "--speak-at" => {
+                let value = args.next().ok_or("Missing value for --speak-at")?;
+                options.speak_at = value
+                    .split(',')
+                    .map(|time| parse_timer(time.trim()))
+                    .collect::<Result<Vec<f64>, String>>()?;
+            }
+            "--speak-command" => {
+                options.speak_command = args.next().ok_or("Missing value for --speak-command")?;
+            }
+            "--smooth-resize" => options.smooth_resize = true,
+            "--inhibit-sleep" => options.inhibit_sleep = true,
+            "--keys" => {
+                let value = args.next().ok_or("Missing value for --keys")?;
+                options.key_bindings = keys::parse_key_bindings(&value)?;
+            }
+            "--numerals" => {
+                let value = args.next().ok_or("Missing value for --numerals")?;
+                options.numerals = parse_numerals(&value)?;
+            }
+            "--remember-geometry" => options.remember_geometry = true,
+            "--mirror" => options.mirror = true,
+            "--mirror-vertical" => options.mirror_vertical = true,
+            "--show-fps" => options.show_fps = true,
+            "--unit-seconds" => {
+                let value = args.next().ok_or("Missing value for --unit-seconds")?;
+                let seconds = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+                    .ok_or(format!("Invalid unit seconds: \"{}\"", value))?;
+
+                options.unit_seconds = Some(seconds);
+            }
+            "--unit-label" => {
+                options.unit_label = args.next().ok_or("Missing value for --unit-label")?;
+            }
+            "--preroll" => {
+                let value = args.next().ok_or("Missing value for --preroll")?;
+                options.preroll = value
+                    .parse::<u32>()
+                    .map_err(|_| format!("Invalid preroll: \"{}\"", value))?;
+            }
+            "--preroll-beeps" => options.preroll_beeps = true,
+            "--list-fonts" => options.list_fonts = true,
+            "--flip-clock" => options.flip_clock = true,
+            "--minimized" => options.minimized = true,
+            "--scale-with-monitor-dpi" => options.scale_with_monitor_dpi = true,
+            "--laps-out" => {
+                options.laps_out = Some(args.next().ok_or("Missing value for --laps-out")?);
+            }
+            "--confirm-quit" => options.confirm_quit = true,
+            "--lcd" => options.lcd = true,
+            "--pause-on-blur" => options.pause_on_blur = true,
+            "--write-to" => {
+                options.write_to = Some(args.next().ok_or("Missing value for --write-to")?);
+            }
+            "--warn-at" => {
+                let value = args.next().ok_or("Missing value for --warn-at")?;
+                options.warn_at = value
+                    .split(',')
+                    .map(|time| parse_timer(time.trim()))
+                    .collect::<Result<Vec<f64>, String>>()?;
+            }
+            "--warn-flash" => options.warn_flash = true,
+            "--no-warn-flash" => options.warn_flash = false,
+            "--padding" => {
+                let value = args.next().ok_or("Missing value for --padding")?;
+                options.padding =
+                    parse_fraction(&value).ok_or(format!("Invalid padding: \"{}\"", value))?;
+            }
+            "--text-size" => {
+                let value = args.next().ok_or("Missing value for --text-size")?;
+                options.text_size = parse_fraction(&value)
+                    .filter(|size| *size > 0.0)
+                    .ok_or(format!("Invalid text size: \"{}\"", value))?;
+            }
+            "--dvd-trail" => options.dvd_trail = true,
+            "--announce-start" => options.announce_start = true,
+            "--start-template" => {
+                options.start_template = args.next().ok_or("Missing value for --start-template")?;
+            }
+            "--progress-bar" => options.progress_bar = true,
+            "--display" => {
+                let value = args.next().ok_or("Missing value for --display")?;
+                options.display = Some(
+                    value
+                        .parse::<u16>()
+                        .map_err(|_| format!("Invalid display: \"{}\"", value))?
+                        .into(),
+                );
+            }
+            "--fullscreen" => options.fullscreen = true,
+            "--alarm-fade-in" => {
+                let value = args.next().ok_or("Missing value for --alarm-fade-in")?;
+                options.alarm_fade_in = Some(parse_timer(&value)?);
+            }
+            "--theme" => {
+                let value = args.next().ok_or("Missing value for --theme")?;
+                let index = theme::find_theme(&value)?;
+                options.text_color = THEMES[index].text;
+                options.background_color = THEMES[index].background;
+                options.theme = Some(index);
+            }
+            "--grace" => {
+                let value = args.next().ok_or("Missing value for --grace")?;
+                options.grace = Some(parse_timer(&value)?);
+            }
+            "--tile" => {
+                let value = args.next().ok_or("Missing value for --tile")?;
+                options.tile = Some(geometry::parse_edge(&value)?);
+            }
+            "--borderless" => options.borderless = true,
+            "--always-on-top" => options.always_on_top = true,
+            "--audio-device" => {
+                options.audio_device = Some(args.next().ok_or("Missing value for --audio-device")?);
+            }
+            "--list-audio-devices" => options.list_audio_devices = true,
+            "--nag" => options.nag = true,
+            "--vertical" => options.vertical = true,
+            "--test-notification" => options.test_notification = true,
+            "--cue" => {
+                let value = args.next().ok_or("Missing value for --cue")?;
+                options.cues.push(cues::parse_cue(&value)?);
+            }
+            "--invert-final" => {
+                let value = args.next().ok_or("Missing value for --invert-final")?;
+                options.invert_final = Some(parse_timer(&value)?);
+            }
+            "--quiet" => options.quiet = true,
+            "--zoom-final" => options.zoom_final = true,
+            "--show-both" => options.show_both = true,
+            "--min-width-chars" => {
+                let value = args.next().ok_or("Missing value for --min-width-chars")?;
+                options.min_width_chars = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid character count: \"{}\"", value))?,
+                );
+            }
+            "--rpc" => options.rpc = true,
+            "--dump-metrics" => options.dump_metrics = true,
+            "--lock" => options.lock = true,
+            "--resume" => options.resume = true,
+            "--hollow" => options.hollow = true,
+            "--outline" => {
+                let value = args.next().ok_or("Missing value for --outline")?;
+                options.outline_color = Some(color::parse_color(&value)?);
+            }
+            "--max-runtime" => {
+                let value = args.next().ok_or("Missing value for --max-runtime")?;
+                options.max_runtime = Some(parse_timer(&value)?);
+            }
+            "--convert" => {
+                let value = args.next().ok_or("Missing value for --convert")?;
+                options.convert = Some(parse_timer(&value)?);
+            }
+            "--border-flash" => options.border_flash = true,
+            "--border-color" => {
+                let value = args.next().ok_or("Missing value for --border-color")?;
+                options.border_color = color::parse_color(&value)?;
+            }
+            "--border-width" => {
+                let value = args.next().ok_or("Missing value for --border-width")?;
+                options.border_width = parse_fraction(&value)
+                    .filter(|width| *width <= 0.5)
+                    .ok_or(format!("Invalid border width: \"{}\"", value))?;
+            }
+            "--stopwatches" => {
+                let value = args.next().ok_or("Missing value for --stopwatches")?;
+                let count = value
+                    .parse()
+                    .ok()
+                    .filter(|count| (1..=MAX_STOPWATCHES).contains(count))
+                    .ok_or(format!(
+                        "Invalid stopwatch count: \"{}\" (it can be from 1 to {})",
+                        value, MAX_STOPWATCHES
+                    ))?;
+                options.stopwatches = Some(count);
+                options.timer_mode = TimerMode::Stopwatch;
+            }
+            "--chess" => {
+                let value = args.next().ok_or("Missing value for --chess")?;
+                options.chess = Some(chess::parse_chess(&value)?);
+                options.timer_mode = TimerMode::Stopwatch;
+            }
+            "--clipboard" => options.clipboard = true,
+            "--opacity" => {
+                let value = args.next().ok_or("Missing value for --opacity")?;
+                options.opacity =
+                    Some(parse_fraction(&value).ok_or(format!("Invalid opacity: \"{}\"", value))?);
+            }
+            "--next-hour" => options.next = Some(60.0 * 60.0),
+            "--next" => {
+                let value = args.next().ok_or("Missing value for --next")?;
+                options.next = Some(
+                    parse_timer(&value)
+                        .ok()
+                        .filter(|interval| *interval >= 1.0)
+                        .ok_or(format!("Invalid interval: \"{}\"", value))?,
+                );
+            }
+            "--history" => {
+                options.history = Some(args.next().ok_or("Missing value for --history")?);
+            }
+            "--auto-precision" => options.time_format.hundredths_below = Some(60.0),
+            "--auto-precision-below" => {
+                let value = args
+                    .next()
+                    .ok_or("Missing value for --auto-precision-below")?;
+                options.time_format.hundredths_below = Some(parse_timer(&value)?);
+            }
+            "--background-image" => {
+                options.background_image =
+                    Some(args.next().ok_or("Missing value for --background-image")?);
+            }
+            "--background-tile" => options.background_tile = true,
+            "--mirror-file" => {
+                options.mirror_file = Some(args.next().ok_or("Missing value for --mirror-file")?);
+                options.timer_mode = TimerMode::Stopwatch;
+            }
+            "--round-start" => options.round_start = true,
+            "--metronome" => {
+                let value = args.next().ok_or("Missing value for --metronome")?;
+                options.metronome = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|bpm: &f64| *bpm > 0.0 && bpm.is_finite())
+                        .ok_or(format!("Invalid tempo: \"{}\"", value))?,
+                );
+            }
+            "--metronome-click" => options.metronome_click = true,
+            "--repeat-notification" => {
+                let value = args
+                    .next()
+                    .ok_or("Missing value for --repeat-notification")?;
+                let interval = parse_timer(&value)?;
+
+                if interval <= 0.0 {
+                    return Err(format!("Invalid interval: \"{}\"", value));
+                }
+                options.repeat_notification = Some(interval);
+            }
+            "--once" => {
+                let value = args.next().ok_or("Missing value for --once")?;
+                once::check_name(&value)?;
+                options.once = Some(value);
+            }
+            "--show-percent" => options.show_percent = true,
+            "--speak-countdown" => {
+                let value = args.next().ok_or("Missing value for --speak-countdown")?;
+                options.speak_countdown = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid countdown seconds: \"{}\"", value))?,
+                );
+            }
+            "--dvd-multi" => {
+                let value = args.next().ok_or("Missing value for --dvd-multi")?;
+                options.dvd_logos = value
+                    .parse()
+                    .ok()
+                    .filter(|count| (1..=MAX_DVD_LOGOS).contains(count))
+                    .ok_or(format!(
+                        "Invalid logo count: \"{}\" (it can be from 1 to {})",
+                        value, MAX_DVD_LOGOS
+                    ))?;
+                options.display_mode = DisplayMode::DVD;
+            }
+            // Anything else that looks like an option is a typo rather than
+            // a timer, so it's better to say so than fail to parse a time.
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}", arg)),
+            _ => options.timer = Some(parse_timer(&arg)?),
+        }
+    }
+
+    if options.chess.is_some() && options.stopwatches.is_some() {
+        return Err(
+            "Invalid options: --chess and --stopwatches can't be used together".to_string(),
+        );
+    }
+
+    if options.rpc && options.read_stdin {
+        return Err("Invalid options: --rpc and --stdin both read from stdin".to_string());
+    }
+
+    if options.mirror_file.is_some() && options.headless {
+        return Err(
+            "Invalid options: --mirror-file needs a window to show the time in".to_string(),
+        );
+    }
+
+    // Rounding up only applies once the hundredths are hidden
+    if options.time_format.round_up
+        && !options.time_format.hide_hundredths
+        && options.time_format.hundredths_below.is_none()
+    {
+        return Err(
+            "Invalid options: --round-up needs --hide-hundredths or --auto-precision".to_string(),
+        );
+    }
+
+    if options.background_tile && options.background_image.is_none() {
+        return Err(
+            "Invalid options: --background-tile needs a --background-image to tile".to_string(),
+        );
+    }
+
+    if options.start_paused && options.headless {
+        return Err(
+            "Invalid options: --start-paused needs a window to start the timer from".to_string(),
+        );
+    }
+
+    if options.padding * 2.0 + options.text_size > 1.0 + f32::EPSILON {
+        return Err(format!(
+            "Invalid layout: a text size of {} with padding of {} on each side doesn't fit in the window",
+            options.text_size, options.padding
+        ));
+    }
+
+    Ok(options)
+}
+
+/// Parse a fraction from 0 to 1, given either as a decimal ("0.1") or a percentage ("10%")
+fn parse_fraction(value: &str) -> Option<f32> {
+    let fraction = match value.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok()? / 100.0,
+        None => value.parse::<f32>().ok()?,
+    };
+
+    (0.0..=1.0).contains(&fraction).then_some(fraction)
+}
+
+#[cfg(test)]
+fn parse(args: &[&str]) -> Result<Options, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn it_should_parse_the_timer_and_options() {
+    let options = parse(&["--dvd", "1:30", "--color", "red"]).unwrap();
+
+    assert_eq!(Some(90.0), options.timer);
+    assert!(options.display_mode == DisplayMode::DVD);
+    assert_eq!(Color::RGB(255, 0, 0), options.text_color);
+}
+
+#[test]
+fn it_should_only_round_up_with_the_hundredths_hidden() {
+    assert!(parse(&["10", "--round-up"]).is_err());
+    assert!(parse(&["10", "--round-up", "--hide-hundredths"]).is_ok());
+    assert!(parse(&["10", "--auto-precision", "--round-up"]).is_ok());
+}
+
+#[test]
+fn it_should_only_tile_a_background_image() {
+    assert!(parse(&["10", "--background-tile"]).is_err());
+    assert!(
+        parse(&["10", "--background-image", "logo.png", "--background-tile"])
+            .unwrap()
+            .background_tile
+    );
+}
+
+#[test]
+fn it_should_only_start_paused_with_a_window() {
+    assert!(parse(&["10", "--start-paused"]).unwrap().start_paused);
+    assert!(parse(&["10", "--start-paused", "--headless"]).is_err());
+}
+
+#[test]
+fn it_should_reject_unknown_options() {
+    assert_eq!(
+        Err("Unknown option: --colour".to_string()),
+        parse(&["--colour", "red"]).map(|_| ())
+    );
+    assert_eq!(
+        Err("Unknown option: --dvdd".to_string()),
+        parse(&["10", "--dvdd"]).map(|_| ())
+    );
+}
+
+#[test]
+fn it_should_reject_an_option_missing_its_value() {
+    assert!(parse(&["10", "--color"]).is_err());
+}
+
+#[test]
+fn it_should_let_the_last_warn_flash_option_win() {
+    assert!(
+        parse(&["10", "--no-warn-flash", "--warn-flash"])
+            .unwrap()
+            .warn_flash
+    );
+    assert!(
+        !parse(&["10", "--warn-flash", "--no-warn-flash"])
+            .unwrap()
+            .warn_flash
+    );
+    assert!(!parse(&["10"]).unwrap().warn_flash);
+}
+
+#[test]
+fn it_should_parse_the_padding_and_text_size_as_fractions_or_percentages() {
+    let options = parse(&["10", "--padding", "5%", "--text-size", "0.9"]).unwrap();
+
+    assert_eq!(0.05, options.padding);
+    assert_eq!(0.9, options.text_size);
+}
+
+#[test]
+fn it_should_reject_a_layout_that_does_not_fit_in_the_window() {
+    assert!(parse(&["10", "--padding", "0.2", "--text-size", "0.8"]).is_err());
+    assert!(parse(&["10", "--text-size", "0"]).is_err());
+    assert!(parse(&["10", "--padding", "150%"]).is_err());
+    assert!(parse(&["10", "--padding", "0.25", "--text-size", "50%"]).is_ok());
+}
+
+#[test]
+fn it_should_only_take_a_shadow_offset_from_zero_to_one() {
+    assert_eq!(
+        0.05,
+        parse(&["10", "--shadow-offset", "5%"])
+            .unwrap()
+            .shadow_offset
+    );
+    assert!(parse(&["10", "--shadow-offset", "-0.1"]).is_err());
+    assert!(parse(&["10", "--shadow-offset", "NaN"]).is_err());
+    assert!(parse(&["10", "--shadow-offset", "inf"]).is_err());
+}
+
+#[test]
+fn it_should_use_the_colors_of_the_last_theme_or_color_given() {
+    let options = parse(&["10", "--theme", "light"]).unwrap();
+    assert_eq!(Color::RGB(0, 0, 0), options.text_color);
+    assert_eq!(Color::RGB(255, 255, 255), options.background_color);
+    assert_eq!(Some(1), options.theme);
+
+    let options = parse(&["10", "--theme", "light", "--color", "red"]).unwrap();
+    assert_eq!(Color::RGB(255, 0, 0), options.text_color);
+    assert_eq!(None, options.theme);
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::clock::Clock;
+use crate::dirs::state_dir;
+use crate::state::TimerState;
+
+const RESUME_FILE: &str = "resume";
+
+/// Where the countdown was when the timer quit early, for `--resume` to pick it back up
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SavedTimer {
+    pub initial: f64,
+    pub remaining: f64,
+    pub paused: bool,
+}
+
+impl fmt::Display for SavedTimer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let paused = match self.paused {
+            true => "paused",
+            _ => "running",
+        };
+
+        writeln!(f, "{} {} {}", self.initial, self.remaining, paused)
+    }
+}
+
+impl FromStr for SavedTimer {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<SavedTimer, String> {
+        let invalid = || format!("Invalid saved timer: \"{}\"", value.trim());
+        let parts: Vec<&str> = value.split_whitespace().collect();
+
+        let saved = match parts[..] {
+            [initial, remaining, paused] => SavedTimer {
+                initial: initial.parse().map_err(|_| invalid())?,
+                remaining: remaining.parse().map_err(|_| invalid())?,
+                paused: match paused {
+                    "paused" => true,
+                    "running" => false,
+                    _ => return Err(invalid()),
+                },
+            },
+            _ => return Err(invalid()),
+        };
+
+        // The countdown can only have so much left of what it started with
+        match saved.initial.is_finite()
+            && saved.remaining.is_finite()
+            && saved.remaining >= 0.0
+            && saved.remaining <= saved.initial
+        {
+            true => Ok(saved),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Read the countdown saved by the last run, if it quit before finishing
+pub fn load() -> Option<SavedTimer> {
+    let path = state_dir()?.join(RESUME_FILE);
+    let contents = std::fs::read_to_string(path).ok()?;
+
+    contents
+        .parse()
+        .map_err(|e| crate::warn(format!("ignoring the saved timer: {}", e)))
+        .ok()
+}
+
+pub fn save(saved: SavedTimer) -> Result<(), String> {
+    let dir = state_dir().ok_or("Could not find a directory to save the timer in")?;
+
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(RESUME_FILE), saved.to_string()))
+        .map_err(|e| format!("Could not save the timer: {}", e))
+}
+
+/// Forget the saved countdown once it's finished, so the next run starts fresh
+pub fn clear() {
+    if let Some(dir) = state_dir() {
+        let _ = std::fs::remove_file(dir.join(RESUME_FILE));
+    }
+}
+
+/// Save the countdown if it's stopping partway through, or forget
+/// the saved one if it's finished
+pub fn save_or_clear(state: &TimerState, finished: bool, clock: &impl Clock) {
+    match !finished && state.is_active() {
+        true => {
+            if let Err(e) = save(SavedTimer {
+                initial: state.initial,
+                remaining: state.display_time(clock),
+                paused: state.paused,
+            }) {
+                crate::warn(e);
+            }
+        }
+        _ => clear(),
+    }
+}
+
+#[test]
+fn it_should_read_back_a_paused_timer() {
+    let saved = SavedTimer {
+        initial: 1500.0,
+        remaining: 612.25,
+        paused: true,
+    };
+
+    assert_eq!(Ok(saved), saved.to_string().parse());
+}
+
+#[test]
+fn it_should_reject_a_malformed_saved_timer() {
+    assert!("".parse::<SavedTimer>().is_err());
+    assert!("1500 612".parse::<SavedTimer>().is_err());
+    assert!("1500 612 stopped".parse::<SavedTimer>().is_err());
+}
+
+#[test]
+fn it_should_reject_a_saved_timer_that_cannot_be_resumed() {
+    assert!("NaN 612 paused".parse::<SavedTimer>().is_err());
+    assert!("inf 612 paused".parse::<SavedTimer>().is_err());
+    assert!("1500 -1 paused".parse::<SavedTimer>().is_err());
+    assert!("-1500 -1600 paused".parse::<SavedTimer>().is_err());
+    assert!("1500 1600 running".parse::<SavedTimer>().is_err());
+}
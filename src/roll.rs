@@ -0,0 +1,101 @@
+use std::time::Instant;
+
+// How long it takes a digit to roll over to its new value, in seconds
+const ROLL_DURATION: f32 = 0.2;
+
+struct RollingDigit {
+    current: char,
+    previous: char,
+    changed_at: Option<Instant>,
+}
+
+/// Keeps track of when each character of the time last changed, so
+/// a changed digit can slide into place like on a mechanical clock.
+pub struct DigitRoll {
+    digits: Vec<RollingDigit>,
+}
+
+impl DigitRoll {
+    pub fn new() -> DigitRoll {
+        DigitRoll { digits: Vec::new() }
+    }
+
+    /// Bring the digits up to date with `text`. Any that changed start rolling.
+    pub fn update(&mut self, text: &str, now: Instant) {
+        // Text of a different length (like a message replacing the time)
+        // doesn't line up with the old digits, so it just appears.
+        if text.chars().count() != self.digits.len() {
+            self.digits = text
+                .chars()
+                .map(|c| RollingDigit {
+                    current: c,
+                    previous: c,
+                    changed_at: None,
+                })
+                .collect();
+            return;
+        }
+
+        for (digit, c) in self.digits.iter_mut().zip(text.chars()) {
+            if digit.current != c {
+                digit.previous = digit.current;
+                digit.current = c;
+                digit.changed_at = Some(now);
+            }
+        }
+    }
+
+    /// Each character along with the one it's rolling away from and how
+    /// far through the roll it is, from 0 when it starts to 1 once it's done.
+    pub fn frames(&self, now: Instant) -> impl Iterator<Item = (char, char, f32)> + '_ {
+        self.digits.iter().map(move |digit| {
+            let progress = digit.changed_at.map_or(1.0, |changed_at| {
+                f32::min((now - changed_at).as_secs_f32() / ROLL_DURATION, 1.0)
+            });
+
+            (digit.current, digit.previous, progress)
+        })
+    }
+}
+
+#[test]
+fn it_should_roll_the_digits_that_changed() {
+    let start = Instant::now();
+    let mut roll = DigitRoll::new();
+    roll.update("01:10", start);
+    roll.update("01:09", start);
+
+    let halfway = start + std::time::Duration::from_secs_f32(ROLL_DURATION / 2.0);
+    let frames: Vec<(char, char, f32)> = roll.frames(halfway).collect();
+
+    assert_eq!(('0', '0', 1.0), frames[0]);
+    assert_eq!((':', ':', 1.0), frames[2]);
+    assert_eq!(('0', '1', 0.5), frames[3]);
+    assert_eq!(('9', '0', 0.5), frames[4]);
+}
+
+#[test]
+fn it_should_finish_rolling_after_the_roll_duration() {
+    let start = Instant::now();
+    let mut roll = DigitRoll::new();
+    roll.update("1", start);
+    roll.update("2", start);
+
+    let later = start + std::time::Duration::from_secs(1);
+    assert_eq!(
+        vec![('2', '1', 1.0)],
+        roll.frames(later).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_should_not_roll_text_of_a_different_length() {
+    let start = Instant::now();
+    let mut roll = DigitRoll::new();
+    roll.update("00:01", start);
+    roll.update("Done", start);
+
+    assert!(roll
+        .frames(start)
+        .all(|(current, previous, progress)| current == previous && progress == 1.0));
+}
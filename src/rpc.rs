@@ -0,0 +1,80 @@
+use std::sync::mpsc::{self, Receiver};
+
+use serde_json::Value;
+
+/// A command read from stdin with `--rpc`
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Start the countdown over from this many seconds
+    Set(f64),
+    Pause,
+    Resume,
+    Restart,
+    Quit,
+}
+
+/// Parse a line of JSON like `{"cmd":"set","seconds":300}` into a command
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let value: Value = serde_json::from_str(line).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let cmd = value
+        .get("cmd")
+        .and_then(Value::as_str)
+        .ok_or("Missing \"cmd\"")?;
+
+    match cmd {
+        "set" => value
+            .get("seconds")
+            .and_then(Value::as_f64)
+            .filter(|seconds| *seconds >= 0.0)
+            .map(Command::Set)
+            .ok_or(format!("Invalid seconds for \"set\": {}", line)),
+        "pause" => Ok(Command::Pause),
+        "resume" => Ok(Command::Resume),
+        "restart" => Ok(Command::Restart),
+        "quit" => Ok(Command::Quit),
+        _ => Err(format!("Unknown command: \"{}\"", cmd)),
+    }
+}
+
+/// Read commands from stdin on their own thread, so that the main loop can pick
+/// them up each frame without waiting on them. A line that isn't a valid command
+/// comes through as an error to report back.
+pub fn read_commands() -> Receiver<Result<Command, String>> {
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines().map_while(Result::ok) {
+            if !line.trim().is_empty() && sender.send(parse_command(&line)).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Write an event to stdout as a line of JSON
+pub fn emit(event: Value) {
+    println!("{}", event);
+}
+
+#[test]
+fn it_should_parse_each_command() {
+    assert_eq!(
+        Ok(Command::Set(300.0)),
+        parse_command(r#"{"cmd":"set","seconds":300}"#)
+    );
+    assert_eq!(Ok(Command::Pause), parse_command(r#"{"cmd": "pause"}"#));
+    assert_eq!(Ok(Command::Resume), parse_command(r#"{"cmd":"resume"}"#));
+    assert_eq!(Ok(Command::Restart), parse_command(r#"{"cmd":"restart"}"#));
+    assert_eq!(Ok(Command::Quit), parse_command(r#"{"cmd":"quit"}"#));
+}
+
+#[test]
+fn it_should_reject_a_malformed_command() {
+    assert!(parse_command("pause").is_err());
+    assert!(parse_command(r#"{"seconds":300}"#).is_err());
+    assert!(parse_command(r#"{"cmd":"set"}"#).is_err());
+    assert!(parse_command(r#"{"cmd":"set","seconds":-1}"#).is_err());
+    assert!(parse_command(r#"{"cmd":"rewind"}"#).is_err());
+}
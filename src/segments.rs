@@ -0,0 +1,149 @@
+use sdl2::rect::Rect;
+
+// How wide each kind of character is drawn, relative to its height
+const DIGIT_WIDTH: f32 = 0.55;
+const SEPARATOR_WIDTH: f32 = 0.25;
+// How thick each segment is, relative to the height of the digit
+const SEGMENT_THICKNESS: f32 = 0.1;
+// The gap between characters, relative to the height of the digit
+const CHARACTER_SPACING: f32 = 0.08;
+
+/// The lit segments of a seven segment display, in the usual order:
+/// a (top), b (top right), c (bottom right), d (bottom), e (bottom left),
+/// f (top left), and g (middle).
+pub fn lit_segments(c: char) -> Option<[bool; 7]> {
+    let segments = match c {
+        '0' => [true, true, true, true, true, true, false],
+        '1' => [false, true, true, false, false, false, false],
+        '2' => [true, true, false, true, true, false, true],
+        '3' => [true, true, true, true, false, false, true],
+        '4' => [false, true, true, false, false, true, true],
+        '5' => [true, false, true, true, false, true, true],
+        '6' => [true, false, true, true, true, true, true],
+        '7' => [true, true, true, false, false, false, false],
+        '8' => [true, true, true, true, true, true, true],
+        '9' => [true, true, true, true, false, true, true],
+        '-' => [false, false, false, false, false, false, true],
+        ' ' => [false; 7],
+        _ => return None,
+    };
+
+    Some(segments)
+}
+
+/// Whether every character of `text` can be drawn with segments
+pub fn can_draw(text: &str) -> bool {
+    text.chars()
+        .all(|c| c == ':' || c == '.' || lit_segments(c).is_some())
+}
+
+fn character_width(c: char) -> f32 {
+    match c {
+        ':' | '.' => SEPARATOR_WIDTH,
+        _ => DIGIT_WIDTH,
+    }
+}
+
+/// Where each of the seven segments goes for a digit drawn in `cell`
+fn segment_rects(cell: Rect) -> [Rect; 7] {
+    let (x, y) = (cell.x(), cell.y());
+    let (w, h) = (cell.width() as i32, cell.height() as i32);
+    let t = ((h as f32 * SEGMENT_THICKNESS) as i32).max(1);
+    let middle = y + h / 2;
+    let horizontal = (w - 2 * t).max(1) as u32;
+    let vertical = (h / 2 - t - t / 2).max(1) as u32;
+
+    [
+        Rect::new(x + t, y, horizontal, t as u32),
+        Rect::new(x + w - t, y + t, t as u32, vertical),
+        Rect::new(x + w - t, middle + t / 2, t as u32, vertical),
+        Rect::new(x + t, y + h - t, horizontal, t as u32),
+        Rect::new(x, middle + t / 2, t as u32, vertical),
+        Rect::new(x, y + t, t as u32, vertical),
+        Rect::new(x + t, middle - t / 2, horizontal, t as u32),
+    ]
+}
+
+/// The dots of a separator drawn in `cell`. A colon has two and a decimal point has one.
+fn separator_rects(c: char, cell: Rect) -> Vec<Rect> {
+    let t = ((cell.height() as f32 * SEGMENT_THICKNESS) as u32).max(1);
+    let x = cell.x() + (cell.width() as i32 - t as i32) / 2;
+    let h = cell.height() as i32;
+
+    match c {
+        ':' => vec![
+            Rect::new(x, cell.y() + h / 3 - t as i32 / 2, t, t),
+            Rect::new(x, cell.y() + 2 * h / 3 - t as i32 / 2, t, t),
+        ],
+        _ => vec![Rect::new(x, cell.y() + h - t as i32, t, t)],
+    }
+}
+
+/// Lay `text` out across `area`, returning the rects to draw lit and the
+/// rects of the unlit segments, which are drawn faintly behind them.
+pub fn layout(text: &str, area: Rect) -> (Vec<Rect>, Vec<Rect>) {
+    let height = area.height() as f32;
+    let natural_width: f32 = text
+        .chars()
+        .map(|c| (character_width(c) + CHARACTER_SPACING) * height)
+        .sum();
+
+    // Squash the characters to fit the width rather than overflowing it
+    let scale = f32::min(area.width() as f32 / natural_width.max(1.0), 1.0);
+    let mut x = area.x() as f32 + (area.width() as f32 - natural_width * scale) / 2.0;
+
+    let mut lit = Vec::new();
+    let mut unlit = Vec::new();
+
+    for c in text.chars() {
+        let width = character_width(c) * height * scale;
+        let cell = Rect::new(x as i32, area.y(), width as u32, area.height());
+
+        match lit_segments(c) {
+            Some(segments) => {
+                for (rect, on) in segment_rects(cell).into_iter().zip(segments) {
+                    match on {
+                        true => lit.push(rect),
+                        _ => unlit.push(rect),
+                    }
+                }
+            }
+            None => lit.extend(separator_rects(c, cell)),
+        }
+
+        x += width + CHARACTER_SPACING * height * scale;
+    }
+
+    (lit, unlit)
+}
+
+#[test]
+fn it_should_light_the_segments_for_each_digit() {
+    let count = |c| lit_segments(c).unwrap().iter().filter(|lit| **lit).count();
+
+    assert_eq!(6, count('0'));
+    assert_eq!(2, count('1'));
+    assert_eq!(5, count('2'));
+    assert_eq!(7, count('8'));
+    assert_eq!(
+        Some([false, true, true, false, false, false, false]),
+        lit_segments('1')
+    );
+    assert_eq!(None, lit_segments('x'));
+}
+
+#[test]
+fn it_should_only_draw_digits_and_separators() {
+    assert!(can_draw("01:23:45.67"));
+    assert!(!can_draw("Time's up"));
+}
+
+#[test]
+fn it_should_lay_out_every_segment_of_each_digit() {
+    let (lit, unlit) = layout("8:1", Rect::new(0, 0, 400, 100));
+
+    // Every segment of the 8, both dots of the colon, and two segments of the 1
+    assert_eq!(7 + 2 + 2, lit.len());
+    assert_eq!(5, unlit.len());
+    assert!(lit.iter().chain(&unlit).all(|rect| rect.right() <= 400));
+}
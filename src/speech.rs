@@ -0,0 +1,110 @@
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "macos")]
+pub const DEFAULT_COMMAND: &str = "say";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_COMMAND: &str = "spd-say";
+
+// The numbers that are spelled out, so that every speech command says them the same way
+const NUMBERS: [&str; 10] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+];
+
+/// Speak the text by running the text to speech command with the text as its
+/// last argument. This doesn't wait for the speech to finish, and a missing
+/// command is quietly ignored since speech is only ever an extra.
+pub fn speak(command: &str, text: &str) {
+    let mut parts = command.split_whitespace();
+
+    if let Some(program) = parts.next() {
+        let child = Command::new(program)
+            .args(parts)
+            .arg(text)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        // The command is waited on out of the way, so that it's cleaned up once it's
+        // done rather than being left behind as a zombie until the timer quits
+        if let Ok(mut child) = child {
+            std::thread::spawn(move || child.wait());
+        }
+    }
+}
+
+/// Describe the remaining time the way it would be said out loud (e.g. "1 hour 5 minutes remaining")
+pub fn describe_remaining(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+
+    // With nothing left, there's no time to describe
+    if seconds == 0 {
+        return "time's up".to_string();
+    }
+    let parts = [
+        (seconds / 60 / 60, "hour"),
+        (seconds / 60 % 60, "minute"),
+        (seconds % 60, "second"),
+    ];
+
+    let description = parts
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| match amount {
+            1 => format!("1 {}", unit),
+            _ => format!("{} {}s", amount, unit),
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    format!("{} remaining", description)
+}
+
+/// Find what to say, if anything, as a countdown goes from `previous` to `current` seconds
+/// remaining. Each of the last `seconds` whole seconds is said as it's crossed, and "time"
+/// is said when the countdown reaches zero.
+pub fn countdown_words(previous: f64, current: f64, seconds: u32) -> Option<String> {
+    if previous > 0.0 && current <= 0.0 {
+        return Some("time".to_string());
+    }
+
+    (1..=seconds)
+        .rev()
+        .find(|second| previous > *second as f64 && current <= *second as f64)
+        .map(|second| match NUMBERS.get(second as usize - 1) {
+            Some(word) => word.to_string(),
+            _ => second.to_string(),
+        })
+}
+
+#[test]
+fn it_should_count_down_the_last_seconds_in_words() {
+    assert_eq!(Some("three".to_string()), countdown_words(3.02, 2.99, 3));
+    assert_eq!(Some("one".to_string()), countdown_words(1.01, 0.98, 3));
+    assert_eq!(Some("time".to_string()), countdown_words(0.01, 0.0, 3));
+    assert_eq!(Some("15".to_string()), countdown_words(15.01, 14.99, 15));
+
+    // Nothing's said before the last few seconds, or between them
+    assert_eq!(None, countdown_words(4.02, 3.99, 3));
+    assert_eq!(None, countdown_words(2.5, 2.4, 3));
+}
+
+#[test]
+fn it_should_describe_whole_minutes() {
+    assert_eq!("5 minutes remaining", describe_remaining(300.0));
+    assert_eq!("1 minute remaining", describe_remaining(60.0));
+}
+
+#[test]
+fn it_should_describe_each_part_of_the_remaining_time() {
+    assert_eq!(
+        "1 hour 1 minute 30 seconds remaining",
+        describe_remaining(3690.0)
+    );
+    assert_eq!("2 hours remaining", describe_remaining(7200.0));
+}
+
+#[test]
+fn it_should_say_when_there_is_no_time_left() {
+    assert_eq!("time's up", describe_remaining(0.0));
+    assert_eq!("time's up", describe_remaining(0.4));
+}
@@ -0,0 +1,319 @@
+use crate::clock::Clock;
+
+#[cfg(test)]
+use crate::clock::FakeClock;
+
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimerMode {
+    Default,
+    Stopwatch,
+}
+
+pub struct TimerState {
+    pub mode: TimerMode,
+    // The time the countdown started from
+    pub initial: f64,
+    // The seconds left in a countdown, or the seconds counted by a stopwatch
+    pub timer: f64,
+    pub paused: bool,
+    // The seconds left in the preroll before the timer itself starts counting
+    pub preroll: f64,
+    last_update: Instant,
+}
+
+impl TimerState {
+    pub fn new(mode: TimerMode, initial: f64, clock: &impl Clock) -> TimerState {
+        TimerState {
+            mode,
+            initial,
+            timer: initial,
+            paused: false,
+            preroll: 0.0,
+            last_update: clock.now(),
+        }
+    }
+
+    /// Hold off counting the timer for a few seconds first
+    pub fn with_preroll(mut self, seconds: f64) -> TimerState {
+        self.preroll = seconds;
+        self
+    }
+
+    /// The timer is always considered active in Stopwatch mode.
+    pub fn is_active(&self) -> bool {
+        self.timer > 0.0 || self.mode == TimerMode::Stopwatch
+    }
+
+    /// Count the time that's passed since the last update and
+    /// return what the timer was before it was brought up to date.
+    pub fn update(&mut self, clock: &impl Clock) -> f64 {
+        let now = clock.now();
+        let mut dt = (now - self.last_update).as_secs_f64();
+        let previous_timer = self.timer;
+        self.last_update = now;
+
+        // The preroll uses up the time first, and anything
+        // left over goes towards the timer itself.
+        if self.preroll > 0.0 && !self.paused {
+            let preroll_time = f64::min(dt, self.preroll);
+            self.preroll -= preroll_time;
+            dt -= preroll_time;
+        }
+
+        if self.is_active() && !self.paused {
+            match self.mode {
+                TimerMode::Default => self.timer -= dt,
+                TimerMode::Stopwatch => self.timer += dt,
+            }
+        }
+
+        previous_timer
+    }
+
+    pub fn toggle_pause(&mut self, clock: &impl Clock) {
+        if self.is_active() {
+            // Bring the timer up to date first so the time between the
+            // last update and the key press is counted on the right side
+            // of the pause.
+            self.update(clock);
+            self.paused = !self.paused;
+        }
+    }
+
+    /// Pause or resume the timer, doing nothing if it's already that way
+    pub fn set_paused(&mut self, paused: bool, clock: &impl Clock) {
+        if self.paused != paused {
+            self.toggle_pause(clock);
+        }
+    }
+
+    /// Jump the countdown to `timer` seconds remaining, keeping it within the
+    /// countdown's starting time. The time before the jump isn't counted.
+    pub fn seek(&mut self, timer: f64, clock: &impl Clock) {
+        self.timer = timer.clamp(0.0, self.initial);
+        self.last_update = clock.now();
+    }
+
+    pub fn restart(&mut self, initial: f64, clock: &impl Clock) {
+        self.initial = initial;
+        self.timer = initial;
+        self.paused = false;
+        self.preroll = 0.0;
+        self.last_update = clock.now();
+    }
+
+    /// Start the countdown over from its initial time, either running or
+    /// waiting to be started
+    pub fn reset(&mut self, paused: bool, clock: &impl Clock) {
+        self.restart(self.initial, clock);
+        self.paused = paused;
+    }
+
+    /// The timer as of right now, including the time since its last update.
+    /// A countdown never shows less than zero.
+    pub fn display_time(&self, clock: &impl Clock) -> f64 {
+        let since_update = match self.is_active() && !self.paused {
+            true => f64::max(
+                (clock.now() - self.last_update).as_secs_f64() - self.preroll,
+                0.0,
+            ),
+            _ => 0.0,
+        };
+
+        match self.mode {
+            TimerMode::Default => f64::max(self.timer - since_update, 0.0),
+            TimerMode::Stopwatch => self.timer + since_update,
+        }
+    }
+
+    /// The seconds left in the preroll as of right now, or zero once it's over
+    pub fn display_preroll(&self, clock: &impl Clock) -> f64 {
+        match self.paused {
+            true => self.preroll,
+            _ => f64::max(
+                self.preroll - (clock.now() - self.last_update).as_secs_f64(),
+                0.0,
+            ),
+        }
+    }
+
+    /// How long the timer has been counting for as of right now
+    pub fn elapsed(&self, clock: &impl Clock) -> f64 {
+        match self.mode {
+            TimerMode::Default => self.initial - self.display_time(clock),
+            TimerMode::Stopwatch => self.display_time(clock),
+        }
+    }
+}
+
+/// Whether the timer passed the threshold when it moved from `previous` to `current`.
+/// Landing exactly on the threshold counts as crossing it.
+pub fn crossed(previous: f64, current: f64, threshold: f64) -> bool {
+    previous > threshold && current <= threshold
+}
+
+#[test]
+fn it_should_count_down_by_the_time_that_passed() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(2.5);
+    assert_eq!(10.0, state.update(&clock));
+    assert_eq!(7.5, state.timer);
+}
+
+#[test]
+fn it_should_reset_to_the_initial_time_paused_or_not() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(4.0);
+    state.reset(true, &clock);
+    clock.advance(2.0);
+    state.update(&clock);
+    assert!(state.paused);
+    assert_eq!(10.0, state.timer);
+
+    state.reset(false, &clock);
+    clock.advance(2.0);
+    state.update(&clock);
+    assert!(!state.paused);
+    assert_eq!(8.0, state.timer);
+}
+
+#[test]
+fn it_should_count_up_in_stopwatch_mode() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Stopwatch, 0.0, &clock);
+
+    clock.advance(3.0);
+    state.update(&clock);
+    assert_eq!(3.0, state.timer);
+    assert!(state.is_active());
+}
+
+#[test]
+fn it_should_not_count_while_paused() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(1.0);
+    state.toggle_pause(&clock);
+    assert_eq!(9.0, state.timer);
+
+    clock.advance(5.0);
+    state.update(&clock);
+    assert_eq!(9.0, state.timer);
+
+    state.toggle_pause(&clock);
+    clock.advance(2.0);
+    state.update(&clock);
+    assert_eq!(7.0, state.timer);
+}
+
+#[test]
+fn it_should_finish_once_the_countdown_runs_out() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 1.0, &clock);
+
+    clock.advance(1.5);
+    state.update(&clock);
+    assert!(!state.is_active());
+    assert_eq!(0.0, state.display_time(&clock));
+    assert_eq!(1.0, state.elapsed(&clock));
+
+    // A finished timer can't be paused
+    state.toggle_pause(&clock);
+    assert!(!state.paused);
+}
+
+#[test]
+fn it_should_include_the_time_since_the_last_update_when_displayed() {
+    let clock = FakeClock::new();
+    let state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(0.25);
+    assert_eq!(9.75, state.display_time(&clock));
+    assert_eq!(0.25, state.elapsed(&clock));
+}
+
+#[test]
+fn it_should_restart_from_a_new_initial_time() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(4.0);
+    state.toggle_pause(&clock);
+    state.restart(20.0, &clock);
+    assert_eq!(20.0, state.timer);
+    assert_eq!(20.0, state.initial);
+    assert!(!state.paused);
+
+    clock.advance(1.0);
+    state.update(&clock);
+    assert_eq!(19.0, state.timer);
+}
+
+#[test]
+fn it_should_cross_a_threshold_once() {
+    assert!(crossed(60.01, 59.99, 60.0));
+    assert!(crossed(60.01, 60.0, 60.0));
+    assert!(!crossed(60.0, 59.99, 60.0));
+    assert!(!crossed(61.0, 60.5, 60.0));
+}
+
+#[test]
+fn it_should_start_counting_once_the_preroll_is_over() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock).with_preroll(3.0);
+
+    clock.advance(2.0);
+    state.update(&clock);
+    assert_eq!(10.0, state.initial);
+    assert_eq!(10.0, state.timer);
+    assert_eq!(1.0, state.display_preroll(&clock));
+
+    clock.advance(1.5);
+    assert_eq!(9.5, state.display_time(&clock));
+    state.update(&clock);
+    assert_eq!(0.0, state.preroll);
+    assert_eq!(9.5, state.timer);
+    assert_eq!(10.0, state.initial);
+}
+
+#[test]
+fn it_should_not_drift_when_paused_and_resumed_repeatedly() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    for _ in 0..10 {
+        clock.advance(0.25);
+        state.set_paused(true, &clock);
+        state.set_paused(true, &clock);
+        clock.advance(1.0);
+        state.set_paused(false, &clock);
+        state.set_paused(false, &clock);
+    }
+
+    state.update(&clock);
+    assert_eq!(7.5, state.timer);
+}
+
+#[test]
+fn it_should_seek_within_the_countdown() {
+    let clock = FakeClock::new();
+    let mut state = TimerState::new(TimerMode::Default, 10.0, &clock);
+
+    clock.advance(3.0);
+    state.seek(8.0, &clock);
+    clock.advance(1.0);
+    state.update(&clock);
+    assert_eq!(7.0, state.timer);
+
+    state.seek(20.0, &clock);
+    assert_eq!(10.0, state.timer);
+    state.seek(-1.0, &clock);
+    assert!(!state.is_active());
+}
@@ -0,0 +1,99 @@
+use crate::clock::Clock;
+use crate::state::{TimerMode, TimerState};
+
+#[cfg(test)]
+use crate::clock::FakeClock;
+
+use sdl2::keyboard::Keycode;
+
+pub const MAX_STOPWATCHES: usize = 4;
+
+/// Several stopwatches that each start and stop on their own, like one for each
+/// player at a table. Any number of them can be running at the same time.
+pub struct Stopwatches {
+    watches: Vec<TimerState>,
+}
+
+impl Stopwatches {
+    /// `count` stopwatches, all stopped at zero until they're started
+    pub fn new(count: usize, clock: &impl Clock) -> Stopwatches {
+        let watches = (0..count)
+            .map(|_| {
+                let mut watch = TimerState::new(TimerMode::Stopwatch, 0.0, clock);
+                watch.set_paused(true, clock);
+                watch
+            })
+            .collect();
+
+        Stopwatches { watches }
+    }
+
+    /// Start the stopwatch at `index` if it's stopped, or stop it if it's running
+    pub fn toggle(&mut self, index: usize, clock: &impl Clock) {
+        if let Some(watch) = self.watches.get_mut(index) {
+            watch.toggle_pause(clock);
+        }
+    }
+
+    pub fn update(&mut self, clock: &impl Clock) {
+        for watch in self.watches.iter_mut() {
+            watch.update(clock);
+        }
+    }
+
+    /// Each stopwatch's time as of right now, along with whether it's running
+    pub fn times<'a>(&'a self, clock: &'a impl Clock) -> impl Iterator<Item = (f64, bool)> + 'a {
+        self.watches
+            .iter()
+            .map(move |watch| (watch.display_time(clock), !watch.paused))
+    }
+}
+
+/// The stopwatch that a number key starts and stops, counting from 1
+pub fn stopwatch_for_key(keycode: Keycode) -> Option<usize> {
+    match keycode {
+        Keycode::Num1 | Keycode::Kp1 => Some(0),
+        Keycode::Num2 | Keycode::Kp2 => Some(1),
+        Keycode::Num3 | Keycode::Kp3 => Some(2),
+        Keycode::Num4 | Keycode::Kp4 => Some(3),
+        _ => None,
+    }
+}
+
+#[test]
+fn it_should_only_count_the_running_stopwatches() {
+    let clock = FakeClock::new();
+    let mut stopwatches = Stopwatches::new(3, &clock);
+
+    stopwatches.toggle(0, &clock);
+    clock.advance(5.0);
+    stopwatches.toggle(2, &clock);
+    clock.advance(2.0);
+    stopwatches.update(&clock);
+
+    assert_eq!(
+        vec![(7.0, true), (0.0, false), (2.0, true)],
+        stopwatches.times(&clock).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_should_keep_the_time_of_a_stopped_stopwatch() {
+    let clock = FakeClock::new();
+    let mut stopwatches = Stopwatches::new(2, &clock);
+
+    stopwatches.toggle(1, &clock);
+    clock.advance(3.0);
+    stopwatches.toggle(1, &clock);
+    clock.advance(10.0);
+    stopwatches.update(&clock);
+
+    assert_eq!(
+        vec![(0.0, false), (3.0, false)],
+        stopwatches.times(&clock).collect::<Vec<_>>()
+    );
+
+    // A stopwatch that doesn't exist is left alone
+    stopwatches.toggle(3, &clock);
+    assert_eq!(2, stopwatches.times(&clock).count());
+}
@@ -0,0 +1,69 @@
+use sdl2::pixels::Color;
+
+pub struct Theme {
+    pub name: &'static str,
+    pub text: Color,
+    pub background: Color,
+}
+
+/// The built-in themes, in the order the theme key cycles through them
+pub const THEMES: &[Theme] = &[
+    Theme {
+        name: "dark",
+        text: Color::RGB(255, 255, 255),
+        background: Color::RGB(0, 0, 0),
+    },
+    Theme {
+        name: "light",
+        text: Color::RGB(0, 0, 0),
+        background: Color::RGB(255, 255, 255),
+    },
+    Theme {
+        name: "solarized-dark",
+        text: Color::RGB(131, 148, 150),
+        background: Color::RGB(0, 43, 54),
+    },
+    Theme {
+        name: "solarized-light",
+        text: Color::RGB(101, 123, 131),
+        background: Color::RGB(253, 246, 227),
+    },
+    Theme {
+        name: "amber",
+        text: Color::RGB(255, 176, 0),
+        background: Color::RGB(20, 12, 0),
+    },
+    Theme {
+        name: "green",
+        text: Color::RGB(51, 255, 102),
+        background: Color::RGB(0, 20, 8),
+    },
+];
+
+/// Find the index of the theme with the given name
+pub fn find_theme(name: &str) -> Result<usize, String> {
+    THEMES
+        .iter()
+        .position(|theme| theme.name.eq_ignore_ascii_case(name))
+        .ok_or(format!("Invalid theme: \"{}\"", name))
+}
+
+/// The theme after `current`, wrapping around to the first. Colors that
+/// didn't come from a theme move on to the first theme.
+pub fn next_theme(current: Option<usize>) -> usize {
+    current.map_or(0, |current| (current + 1) % THEMES.len())
+}
+
+#[test]
+fn it_should_find_a_theme_by_name() {
+    assert_eq!(Ok(1), find_theme("light"));
+    assert_eq!(Ok(2), find_theme("Solarized-Dark"));
+    assert!(find_theme("neon").is_err());
+}
+
+#[test]
+fn it_should_cycle_through_the_themes() {
+    assert_eq!(0, next_theme(None));
+    assert_eq!(1, next_theme(Some(0)));
+    assert_eq!(0, next_theme(Some(THEMES.len() - 1)));
+}
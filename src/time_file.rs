@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const WRITE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the mirrored time can go without changing before it's shown as stale
+const STALE_AFTER: Duration = Duration::from_secs(3);
+
+/// Shown in place of the time while there's no file to mirror
+const MISSING_TEXT: &str = "--:--";
+
+/// Keeps a file up to date with the time on the clock, for
+/// things like a text source in OBS to read it from.
+pub struct TimeFile {
+    path: PathBuf,
+    last_write: Option<Instant>,
+    last_text: String,
+    failing: bool,
+}
+
+impl TimeFile {
+    pub fn new(path: impl Into<PathBuf>) -> TimeFile {
+        TimeFile {
+            path: path.into(),
+            last_write: None,
+            last_text: String::new(),
+            failing: false,
+        }
+    }
+
+    /// Write the time to the file, at most once a second unless `force` is set.
+    /// Nothing is written if the text hasn't changed since the last write.
+    pub fn write(&mut self, text: &str, force: bool) {
+        let now = Instant::now();
+        let due = force
+            || self
+                .last_write
+                .is_none_or(|last_write| now.duration_since(last_write) >= WRITE_INTERVAL);
+
+        if !due || text == self.last_text {
+            return;
+        }
+
+        self.last_write = Some(now);
+        self.last_text = text.to_string();
+
+        // A failing write is reported once rather than every second
+        match write_atomically(&self.path, text) {
+            Ok(()) => self.failing = false,
+            Err(e) if !self.failing => {
+                crate::warn(format!(
+                    "could not write the time to \"{}\": {}",
+                    self.path.display(),
+                    e
+                ));
+                self.failing = true;
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Follows a file that another timer keeps up to date with `--write-to`,
+/// so its time can be shown here too.
+pub struct MirrorFile {
+    path: PathBuf,
+    last_read: Option<Instant>,
+    last_change: Instant,
+    text: Option<String>,
+}
+
+impl MirrorFile {
+    pub fn new(path: impl Into<PathBuf>) -> MirrorFile {
+        MirrorFile {
+            path: path.into(),
+            last_read: None,
+            last_change: Instant::now(),
+            text: None,
+        }
+    }
+
+    /// Read the file again if it's been a second since it was last read
+    pub fn poll(&mut self) {
+        if self
+            .last_read
+            .is_none_or(|last_read| last_read.elapsed() >= WRITE_INTERVAL)
+        {
+            self.read();
+        }
+    }
+
+    /// Read the time from the file. A file that's missing or empty leaves nothing to show.
+    pub fn read(&mut self) {
+        let now = Instant::now();
+        let text = std::fs::read_to_string(&self.path)
+            .ok()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+
+        if text != self.text {
+            self.text = text;
+            self.last_change = now;
+        }
+        self.last_read = Some(now);
+    }
+
+    /// The time from the file, or a placeholder if there isn't one
+    pub fn text(&self) -> &str {
+        self.text.as_deref().unwrap_or(MISSING_TEXT)
+    }
+
+    /// Whether the time has stopped changing, like when the other timer is
+    /// paused or has quit, or there's no time to show at all
+    pub fn is_stale(&self) -> bool {
+        self.text.is_none() || self.last_change.elapsed() >= STALE_AFTER
+    }
+}
+
+/// Write to a temporary file next to `path` and rename it into place, so that
+/// anything reading the file never sees it half written.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    std::fs::write(&temporary, contents)?;
+    std::fs::rename(&temporary, path)
+}
+
+#[test]
+fn it_should_replace_the_contents_of_the_file() {
+    let path = std::env::temp_dir().join(format!("timer-time-file-{}.txt", std::process::id()));
+    let mut time_file = TimeFile::new(&path);
+
+    time_file.write("00:00:10", false);
+    assert_eq!("00:00:10", std::fs::read_to_string(&path).unwrap());
+
+    // Within the same second only a forced write goes through
+    time_file.write("00:00:09", false);
+    assert_eq!("00:00:10", std::fs::read_to_string(&path).unwrap());
+    time_file.write("00:00:00", true);
+    assert_eq!("00:00:00", std::fs::read_to_string(&path).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn it_should_mirror_the_time_in_the_file() {
+    let path = std::env::temp_dir().join(format!("timer-mirror-file-{}.txt", std::process::id()));
+    let mut mirror = MirrorFile::new(&path);
+
+    mirror.read();
+    assert_eq!(MISSING_TEXT, mirror.text());
+    assert!(mirror.is_stale());
+
+    std::fs::write(&path, "00:04:59\n").unwrap();
+    mirror.read();
+    assert_eq!("00:04:59", mirror.text());
+    assert!(!mirror.is_stale());
+
+    // The last time read is dropped once the file goes away
+    std::fs::remove_file(&path).unwrap();
+    mirror.read();
+    assert_eq!(MISSING_TEXT, mirror.text());
+}